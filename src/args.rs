@@ -1,16 +1,25 @@
 #[cfg(feature = "mine")]
 use criner_cli;
 
-#[cfg(any(feature = "mine", feature = "recent-changes"))]
-use std::path::PathBuf;
+#[cfg(any(feature = "list", feature = "recent-changes"))]
+use crate::filter::Filter;
+#[cfg(any(feature = "list", feature = "recent-changes", feature = "search"))]
+use crate::{output::Columns, template::Template};
 use clap::Clap;
+#[cfg(any(feature = "mine", feature = "recent-changes", feature = "add"))]
+use std::path::PathBuf;
 use std::str::FromStr;
 
 #[allow(non_camel_case_types)]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum OutputKind {
     human,
-    json
+    json,
+    ndjson,
+    yaml,
+    csv,
+    tsv,
+    markdown
 }
 
 impl FromStr for OutputKind {
@@ -20,13 +29,131 @@ impl FromStr for OutputKind {
         Ok(match s {
             "human"|"Human" => OutputKind::human,
             "json"|"Json" => OutputKind::json,
+            "ndjson"|"Ndjson" => OutputKind::ndjson,
+            "yaml"|"Yaml" => OutputKind::yaml,
+            "csv"|"Csv" => OutputKind::csv,
+            "tsv"|"Tsv" => OutputKind::tsv,
+            "markdown"|"Markdown" => OutputKind::markdown,
             _ => return Err(format!("unknown output kind: {:?}", s))
         })
     }
 }
 
 impl OutputKind {
-    pub const VARIANTS: &'static [&'static str] = &["human", "json"];
+    pub const VARIANTS: &'static [&'static str] =
+        &["human", "json", "ndjson", "yaml", "csv", "tsv", "markdown"];
+}
+
+#[cfg(feature = "list")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortBy {
+    Name,
+    Downloads,
+    RecentDownloads,
+    Created,
+    Updated,
+}
+
+#[cfg(feature = "list")]
+impl FromStr for SortBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "name" => SortBy::Name,
+            "downloads" => SortBy::Downloads,
+            "recent-downloads" => SortBy::RecentDownloads,
+            "created" => SortBy::Created,
+            "updated" => SortBy::Updated,
+            _ => return Err(format!("unknown sort order: {:?}", s)),
+        })
+    }
+}
+
+#[cfg(feature = "list")]
+impl SortBy {
+    pub const VARIANTS: &'static [&'static str] = &[
+        "name",
+        "downloads",
+        "recent-downloads",
+        "created",
+        "updated",
+    ];
+}
+
+#[cfg(feature = "list")]
+impl std::fmt::Display for SortBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::VARIANTS[*self as usize])
+    }
+}
+
+#[cfg(feature = "search")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchSort {
+    Relevance,
+    Downloads,
+    RecentDownloads,
+    RecentUpdates,
+    New,
+}
+
+#[cfg(feature = "search")]
+impl Default for SearchSort {
+    fn default() -> Self {
+        SearchSort::Relevance
+    }
+}
+
+#[cfg(feature = "search")]
+impl FromStr for SearchSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "relevance" => SearchSort::Relevance,
+            "downloads" => SearchSort::Downloads,
+            "recent-downloads" => SearchSort::RecentDownloads,
+            "recent-updates" => SearchSort::RecentUpdates,
+            "new" => SearchSort::New,
+            _ => return Err(format!("unknown sort order: {:?}", s)),
+        })
+    }
+}
+
+#[cfg(feature = "search")]
+impl SearchSort {
+    pub const VARIANTS: &'static [&'static str] = &[
+        "relevance",
+        "downloads",
+        "recent-downloads",
+        "recent-updates",
+        "new",
+    ];
+    const ALL: &'static [SearchSort] = &[
+        SearchSort::Relevance,
+        SearchSort::Downloads,
+        SearchSort::RecentDownloads,
+        SearchSort::RecentUpdates,
+        SearchSort::New,
+    ];
+
+    /// The sort order after this one, starting over after the last one.
+    pub fn next(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::ALL.len()]
+    }
+
+    /// The sort order before this one, continuing with the last one after the first one.
+    pub fn previous(self) -> Self {
+        Self::ALL[(self as usize + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+#[cfg(feature = "search")]
+impl std::fmt::Display for SearchSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::VARIANTS[*self as usize])
+    }
 }
 
 #[derive(Debug, Clap)]
@@ -34,6 +161,15 @@ impl OutputKind {
 #[clap(setting = clap::AppSettings::ColoredHelp)]
 #[clap(setting = clap::AppSettings::ColorAuto)]
 pub struct Parsed {
+    #[clap(
+        long,
+        env = "CRATES_IO_API_URL",
+        global = true,
+        default_value = "https://crates.io",
+        name = "URL"
+    )]
+    /// The base URL of a registry serving a crates.io compatible API below '/api/v1'.
+    pub api_url: String,
     #[clap(subcommand)]
     pub sub: Option<SubCommands>,
 }
@@ -56,11 +192,54 @@ pub enum SubCommands {
         #[clap(long = "output", short = 'o', possible_values = &OutputKind::VARIANTS, default_value = "human")]
         /// The type of output to produce
         output_format: OutputKind,
+        #[clap(long = "format", name = "TEMPLATE")]
+        /// A template like '{name}@{max_version}' to print each item with, instead of using --output.
+        /// Fields are named like the keys of the json output.
+        template: Option<Template>,
+        #[clap(long, name = "COLUMNS")]
+        /// Comma separated fields to show as columns of human-readable tables, like 'name,max_version,repository'.
+        /// Fields are named like the keys of the json output.
+        columns: Option<Columns>,
+        #[clap(long, name = "EXPRESSION")]
+        /// Only show changes for which the expression is true, like 'yanked && name ~ "^serde"'.
+        /// Fields are named like the keys of the json output, and can be compared with ==, !=, <, <=, >, >=,
+        /// or matched against regular expressions with ~ and !~. Conditions are combined with &&, || and !.
+        filter: Option<Filter>,
     },
-    /// search crates interactively
+    /// search crates interactively, or print the results for a query
+    ///
+    /// Without a query, an interactive search is started. If standard output is not a terminal, the query is read
+    /// from standard input instead, and its results are printed.
     #[cfg(feature = "search")]
     #[clap(display_order = 2)]
-    Search,
+    Search {
+        /// The search term. If set, the results are printed instead of searching interactively.
+        query: Option<String>,
+        #[clap(long, default_value = "100", name = "N")]
+        /// Show at most N crates
+        limit: u32,
+        #[clap(long, possible_values = &SearchSort::VARIANTS, default_value = "relevance")]
+        /// The order of the crates, as determined by crates.io
+        sort: SearchSort,
+        #[clap(long = "output", short = 'o', possible_values = &OutputKind::VARIANTS, default_value = "human")]
+        /// The type of output to produce
+        output_format: OutputKind,
+        #[clap(long = "format", name = "TEMPLATE")]
+        /// A template like '{name}@{max_version}' to print each item with, instead of using --output.
+        /// Fields are named like the keys of the json output.
+        template: Option<Template>,
+        #[clap(long, name = "COLUMNS")]
+        /// Comma separated fields to show as columns of human-readable tables, like 'name,max_version,repository'.
+        /// Fields are named like the keys of the json output.
+        columns: Option<Columns>,
+        #[clap(long)]
+        /// Do not load or save the history of interactive searches
+        no_history: bool,
+        #[clap(long, name = "SIZE")]
+        /// The amount of search terms to keep in the history of interactive searches, 1000 by default.
+        /// It is kept in 'crates-io-cli/search-history' below $XDG_DATA_HOME or ~/.local/share.
+        history_size: Option<usize>,
+    },
     /// list crates by a particular criterion
     #[clap(display_order = 3)]
     #[cfg(feature = "list")]
@@ -70,21 +249,134 @@ pub enum SubCommands {
         #[clap(long = "output", short = 'o', possible_values = &OutputKind::VARIANTS, default_value = "human")]
         /// The type of output to produce
         output_format: OutputKind,
+        #[clap(long = "format", name = "TEMPLATE")]
+        /// A template like '{name}@{max_version}' to print each item with, instead of using --output.
+        /// Fields are named like the keys of the json output.
+        template: Option<Template>,
+        #[clap(long, name = "COLUMNS")]
+        /// Comma separated fields to show as columns of human-readable tables, like 'name,max_version,repository'.
+        /// Fields are named like the keys of the json output.
+        columns: Option<Columns>,
+        #[clap(long, name = "EXPRESSION")]
+        /// Only show crates for which the expression is true, like 'downloads > 10000 && name ~ "^serde"'.
+        /// Fields are named like the keys of the json output, and can be compared with ==, !=, <, <=, >, >=,
+        /// or matched against regular expressions with ~ and !~. Conditions are combined with &&, || and !.
+        filter: Option<Filter>,
+        #[clap(long, possible_values = &SortBy::VARIANTS)]
+        /// The order of the crates, which is by downloads with --limit and by name otherwise.
+        /// Names are sorted alphabetically, everything else with the largest or latest first.
+        /// Reverse dependencies can only be sorted by name and downloads.
+        sort: Option<SortBy>,
+        #[clap(long)]
+        /// Reverse the order of the crates
+        reverse: bool,
+        #[clap(long, name = "N")]
+        /// Show at most N crates. Only these are fetched when sorting by downloads or recent downloads
+        /// without --filter and --reverse, otherwise all crates are fetched to find them.
+        limit: Option<u32>,
+        #[clap(long, default_value = "0", name = "M")]
+        /// Skip the first M crates
+        offset: u32,
+    },
+    /// show all there is to know about a single crate
+    #[clap(display_order = 4)]
+    #[cfg(feature = "info")]
+    Info {
+        /// The name of the crate, e.g. crates-io-cli
+        name: String,
+        #[clap(long = "output", short = 'o', possible_values = &OutputKind::VARIANTS, default_value = "human")]
+        /// The type of output to produce
+        output_format: OutputKind,
+    },
+    /// list all published versions of a crate
+    #[clap(display_order = 5)]
+    #[cfg(feature = "versions")]
+    Versions {
+        /// The name of the crate, e.g. crates-io-cli
+        name: String,
+        #[clap(long)]
+        /// Do not show versions which were yanked
+        hide_yanked: bool,
+        #[clap(long)]
+        /// Do not show pre-release versions, like 1.0.0-alpha.1
+        hide_prereleases: bool,
+        #[clap(long = "output", short = 'o', possible_values = &OutputKind::VARIANTS, default_value = "human")]
+        /// The type of output to produce
+        output_format: OutputKind,
+    },
+    /// show the users and teams owning one or more crates
+    #[clap(display_order = 6)]
+    #[cfg(feature = "owners")]
+    Owners {
+        /// The names of the crates, e.g. crates-io-cli. If unset, whitespace separated names are read from stdin.
+        names: Vec<String>,
+        #[clap(long = "output", short = 'o', possible_values = &OutputKind::VARIANTS, default_value = "human")]
+        /// The type of output to produce
+        output_format: OutputKind,
+    },
+    /// show how often a crate was downloaded per day
+    #[clap(display_order = 7)]
+    #[cfg(feature = "downloads")]
+    Downloads {
+        /// The name of the crate, e.g. crates-io-cli
+        name: String,
+        #[clap(long)]
+        /// Show the downloads of each version separately instead of their sum
+        by_version: bool,
+        #[clap(long = "output", short = 'o', possible_values = &OutputKind::VARIANTS, default_value = "human")]
+        /// The type of output to produce
+        output_format: OutputKind,
+    },
+    /// add the latest version of a crate to the dependencies of a Cargo.toml
+    ///
+    /// The formatting and comments of the manifest are kept as they are.
+    #[clap(display_order = 8)]
+    #[cfg(feature = "add")]
+    Add {
+        /// The name of the crate, e.g. crates-io-cli
+        name: String,
+        #[clap(long, conflicts_with = "build")]
+        /// Add the crate to the dev-dependencies
+        dev: bool,
+        #[clap(long)]
+        /// Add the crate to the build-dependencies
+        build: bool,
+        #[clap(long, name = "PATH")]
+        /// The Cargo.toml to change. If unset, the one in the current directory or the closest of its parents is used.
+        manifest_path: Option<PathBuf>,
     },
     /// Invoke a hackable miner for crates.io
     #[cfg(feature = "mine")]
-    #[clap(display_order = 4)]
+    #[clap(display_order = 9)]
     Criner(criner_cli::Args),
 }
 
 #[derive(Clap, Debug)]
 pub enum ListCmd {
-    /// crates for the given user id
+    /// crates for the given user login or id
     ByUser {
-        /// The numerical id of your user, e.g. 980. Currently there is no way
-        /// to easily obtain it though, so you will have to debug actual
-        /// crates.io calls in your browser - the /me response contains all
-        /// user data. Use any string to receive *all* crates!
-        id: u32,
+        /// The GitHub login of the user, e.g. Byron, or their numerical id on crates.io, e.g. 980.
+        user: String,
+    },
+    /// crates owned by the given team
+    ByTeam {
+        /// The login of the team in the form of 'github:org:team', e.g. github:rust-lang:core,
+        /// or its numerical id on crates.io.
+        team: String,
+    },
+    /// crates tagged with the given keyword
+    ByKeyword {
+        /// The keyword to look for, e.g. async
+        keyword: String,
+    },
+    /// crates in the given category
+    ByCategory {
+        /// The slug of the category, e.g. parser-implementations
+        category: String,
+    },
+    /// crates depending on the given crate
+    ReverseDeps {
+        /// The name of the crate whose dependents to list, e.g. crates-index-diff
+        name: String,
     },
 }
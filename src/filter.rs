@@ -0,0 +1,425 @@
+//! A small expression language to select records by their fields, like
+//! `downloads > 10000 && name ~ "^serde"` for crates or `yanked && name ~ "^serde"` for versions.
+//!
+//! * fields are named like the keys of the json output, with `.` to access nested ones
+//! * fields which don't exist in a record are `null` there, but a field which exists in no record is an error
+//! * comparisons are `==`, `!=`, `<`, `<=`, `>`, `>=`, as well as `~` and `!~` to match a regular expression
+//! * literals are numbers, "strings" or 'strings', `true`, `false` and `null`
+//! * conditions are combined with `&&`, `||` and `!`, and grouped with parentheses
+//! * a field on its own is true unless it is `null`, `false`, `0`, empty, or doesn't exist
+use crate::template::{compare, plain, Field};
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::{cmp::Ordering, str::FromStr};
+
+quick_error! {
+    #[derive(Debug)]
+    pub enum Error {
+        Syntax(message: String, position: usize) {
+            display("{} at position {}", message, position)
+        }
+        Regex(err: regex::Error) {
+            display("Invalid regular expression: {}", err)
+            from()
+        }
+        Json(err: serde_json::Error) {
+            description("The item could not be converted into fields")
+            from()
+            cause(err)
+        }
+        Field(err: crate::template::Error) {
+            display("{}", err)
+            from()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Literal(Value),
+    Op(&'static str),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+const OPERATORS: &[&str] = &["==", "!=", "<=", ">=", "!~", "<", ">", "~"];
+
+fn tokenize(s: &str) -> Result<Vec<(usize, Token)>, Error> {
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while pos < s.len() {
+        let rest = &s[pos..];
+        let c = rest.chars().next().expect("not at end");
+        if c.is_whitespace() {
+            pos += c.len_utf8();
+            continue;
+        }
+        let (len, token) = if rest.starts_with("&&") {
+            (2, Token::And)
+        } else if rest.starts_with("||") {
+            (2, Token::Or)
+        } else if let Some(op) = OPERATORS.iter().find(|op| rest.starts_with(*op)) {
+            (op.len(), Token::Op(op))
+        } else if c == '!' {
+            (1, Token::Not)
+        } else if c == '(' {
+            (1, Token::Open)
+        } else if c == ')' {
+            (1, Token::Close)
+        } else if c == '"' || c == '\'' {
+            let mut value = String::new();
+            let mut chars = rest.char_indices().skip(1);
+            let end = loop {
+                match chars.next() {
+                    Some((i, q)) if q == c => break i + 1,
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, e)) => value.push(e),
+                        None => return Err(Error::Syntax("Unterminated string".into(), pos)),
+                    },
+                    Some((_, e)) => value.push(e),
+                    None => return Err(Error::Syntax("Unterminated string".into(), pos)),
+                }
+            };
+            (end, Token::Literal(Value::String(value)))
+        } else if c.is_ascii_digit() || c == '-' {
+            let len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.' || c == '-'))
+                .unwrap_or(rest.len());
+            let number = rest[..len]
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .ok_or_else(|| Error::Syntax(format!("Invalid number '{}'", &rest[..len]), pos))?;
+            (len, Token::Literal(Value::Number(number)))
+        } else if c.is_alphabetic() || c == '_' {
+            let len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+                .unwrap_or(rest.len());
+            let token = match &rest[..len] {
+                "true" => Token::Literal(Value::Bool(true)),
+                "false" => Token::Literal(Value::Bool(false)),
+                "null" => Token::Literal(Value::Null),
+                ident => Token::Ident(ident.to_owned()),
+            };
+            (len, token)
+        } else {
+            return Err(Error::Syntax(format!("Unexpected character '{}'", c), pos));
+        };
+        tokens.push((pos, token));
+        pos += len;
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug)]
+enum Operand {
+    Field(Field),
+    Literal(Value),
+}
+
+#[derive(Debug)]
+enum Expr {
+    Or(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Truthy(Operand),
+    Compare(Operand, &'static str, Operand),
+    Matches(Operand, Regex, bool),
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    index: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|(_, t)| t)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.index)
+            .map(|(p, _)| *p)
+            .unwrap_or(self.end)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.index).map(|(_, t)| t.clone());
+        self.index += 1;
+        token
+    }
+
+    fn unexpected(&self, expected: &str) -> Error {
+        let found = match self.peek() {
+            Some(Token::Ident(name)) => format!("'{}'", name),
+            Some(Token::Literal(value)) => value.to_string(),
+            Some(Token::Op(op)) => format!("'{}'", op),
+            Some(Token::And) => "'&&'".into(),
+            Some(Token::Or) => "'||'".into(),
+            Some(Token::Not) => "'!'".into(),
+            Some(Token::Open) => "'('".into(),
+            Some(Token::Close) => "')'".into(),
+            None => "the end".into(),
+        };
+        Error::Syntax(
+            format!("Expected {}, found {}", expected, found),
+            self.position(),
+        )
+    }
+
+    fn or(&mut self) -> Result<Expr, Error> {
+        let mut lhs = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            lhs = Expr::Or(Box::new(lhs), Box::new(self.and()?));
+        }
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> Result<Expr, Error> {
+        let mut lhs = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            lhs = Expr::And(Box::new(lhs), Box::new(self.unary()?));
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr, Error> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.next();
+                Ok(Expr::Not(Box::new(self.unary()?)))
+            }
+            Some(Token::Open) => {
+                self.next();
+                let expr = self.or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => {
+                        self.index -= 1;
+                        Err(self.unexpected("')'"))
+                    }
+                }
+            }
+            _ => self.comparison(),
+        }
+    }
+
+    fn operand(&mut self) -> Result<Operand, Error> {
+        match self.peek().cloned() {
+            Some(Token::Ident(name)) => {
+                let position = self.position();
+                self.next();
+                name.parse()
+                    .map(Operand::Field)
+                    .map_err(|_| Error::Syntax("Invalid field name".into(), position))
+            }
+            Some(Token::Literal(value)) => {
+                self.next();
+                Ok(Operand::Literal(value))
+            }
+            _ => Err(self.unexpected("a field or a value")),
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Expr, Error> {
+        let lhs = self.operand()?;
+        let op = match self.peek() {
+            Some(Token::Op(op)) => *op,
+            _ => return Ok(Expr::Truthy(lhs)),
+        };
+        self.next();
+        if op == "~" || op == "!~" {
+            return match self.next() {
+                Some(Token::Literal(Value::String(pattern))) => {
+                    Ok(Expr::Matches(lhs, Regex::new(&pattern)?, op == "~"))
+                }
+                _ => {
+                    self.index -= 1;
+                    Err(self.unexpected("a string with a regular expression"))
+                }
+            };
+        }
+        Ok(Expr::Compare(lhs, op, self.operand()?))
+    }
+}
+
+impl Expr {
+    /// All fields used in this expression
+    fn fields<'a>(&'a self, fields: &mut Vec<&'a Field>) {
+        let mut operand = |operand: &'a Operand| {
+            if let Operand::Field(field) = operand {
+                fields.push(field)
+            }
+        };
+        match self {
+            Expr::Or(lhs, rhs) | Expr::And(lhs, rhs) => {
+                lhs.fields(fields);
+                rhs.fields(fields);
+            }
+            Expr::Not(expr) => expr.fields(fields),
+            Expr::Truthy(field) | Expr::Matches(field, ..) => operand(field),
+            Expr::Compare(lhs, _, rhs) => {
+                operand(lhs);
+                operand(rhs);
+            }
+        }
+    }
+}
+
+/// An expression which is true or false for a given record.
+#[derive(Debug)]
+pub struct Filter(Expr);
+
+impl FromStr for Filter {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            index: 0,
+            end: s.len(),
+        };
+        let expr = parser.or()?;
+        if parser.peek().is_some() {
+            return Err(parser.unexpected("'&&', '||' or the end"));
+        }
+        Ok(Filter(expr))
+    }
+}
+
+fn resolve<'a>(operand: &'a Operand, item: &'a Value) -> &'a Value {
+    match operand {
+        Operand::Literal(value) => value,
+        Operand::Field(field) => field.lookup(item).unwrap_or(&Value::Null),
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64() != Some(0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(a) => !a.is_empty(),
+        Value::Object(o) => !o.is_empty(),
+    }
+}
+
+fn same_type(a: &Value, b: &Value) -> bool {
+    std::mem::discriminant(a) == std::mem::discriminant(b)
+}
+
+fn eval(expr: &Expr, item: &Value) -> bool {
+    match expr {
+        Expr::Or(lhs, rhs) => eval(lhs, item) || eval(rhs, item),
+        Expr::And(lhs, rhs) => eval(lhs, item) && eval(rhs, item),
+        Expr::Not(expr) => !eval(expr, item),
+        Expr::Truthy(operand) => is_truthy(resolve(operand, item)),
+        Expr::Matches(operand, regex, expected) => match resolve(operand, item) {
+            Value::Null => !expected,
+            value => regex.is_match(&plain(value)) == *expected,
+        },
+        Expr::Compare(lhs, op, rhs) => {
+            let (lhs, rhs) = (resolve(lhs, item), resolve(rhs, item));
+            let ordering = compare(lhs, rhs);
+            match *op {
+                "==" => ordering == Ordering::Equal,
+                "!=" => ordering != Ordering::Equal,
+                _ if !same_type(lhs, rhs) => false,
+                "<" => ordering == Ordering::Less,
+                "<=" => ordering != Ordering::Greater,
+                ">" => ordering == Ordering::Greater,
+                ">=" => ordering != Ordering::Less,
+                _ => unreachable!("all operators are handled"),
+            }
+        }
+    }
+}
+
+impl Filter {
+    /// Keep only the items this filter matches.
+    /// Fails if a field exists in none of the items, which most likely is a typo.
+    pub fn retain<T>(&self, items: Vec<T>) -> Result<Vec<T>, Error>
+    where
+        T: Serialize,
+    {
+        let records = items
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut fields = Vec::new();
+        self.0.fields(&mut fields);
+        for field in fields {
+            if records.iter().all(|record| field.lookup(record).is_err()) {
+                if let Some(Err(err)) = records.first().map(|record| field.lookup(record)) {
+                    return Err(err.into());
+                }
+            }
+        }
+        Ok(items
+            .into_iter()
+            .zip(records)
+            .filter(|(_, record)| eval(&self.0, record))
+            .map(|(item, _)| item)
+            .collect())
+    }
+}
+
+#[test]
+fn test_filter() {
+    let item = serde_json::json!({
+        "name": "serde_json",
+        "downloads": 20000,
+        "yanked": false,
+        "homepage": null,
+        "links": {"owners": "/owners"}
+    });
+    let matches = |f: &str| eval(&f.parse::<Filter>().unwrap().0, &item);
+    assert!(matches(
+        r#"downloads > 10000 && name ~ "^serde" && !yanked"#
+    ));
+    assert!(matches(
+        "downloads >= 20000 && downloads <= 2e4 && downloads != 1"
+    ));
+    assert!(matches(
+        "!(downloads < 10 || name == 'serde') && links.owners"
+    ));
+    assert!(matches(
+        "homepage == null && !homepage && !missing && name !~ 'yaml'"
+    ));
+    assert!(!matches("name > 5 || name < 5"));
+    assert!(!matches(r#"name ~ "^json""#));
+    assert!(matches(r#"name == "serde_json""#));
+
+    let error = |f: &str| f.parse::<Filter>().unwrap_err().to_string();
+    assert_eq!(
+        error("downloads >"),
+        "Expected a field or a value, found the end at position 11"
+    );
+    assert_eq!(error("(name"), "Expected ')', found the end at position 5");
+    assert_eq!(
+        error("name ~ 5"),
+        "Expected a string with a regular expression, found 5.0 at position 7"
+    );
+    assert_eq!(error("a = b"), "Unexpected character '=' at position 2");
+    assert_eq!(error("name == 'x"), "Unterminated string at position 8");
+    assert!(error("name ~ '('").starts_with("Invalid regular expression"));
+
+    let items = vec![item, serde_json::json!({"name": "yaml", "license": "MIT"})];
+    let retain = |f: &str| f.parse::<Filter>().unwrap().retain(items.clone());
+    assert_eq!(retain("license || downloads > 10000").unwrap().len(), 2);
+    assert_eq!(retain("!license").unwrap().len(), 1);
+    assert!(retain("downlods > 10000")
+        .unwrap_err()
+        .to_string()
+        .contains("downlods"));
+}
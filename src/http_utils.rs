@@ -13,7 +13,8 @@ use std::{
     sync::Mutex,
 };
 
-const MAX_ITEMS_PER_PAGE: u32 = 100;
+/// The largest amount of items crates.io returns per page
+pub const MAX_ITEMS_PER_PAGE: u32 = 100;
 
 #[must_use = "futures do nothing unless polled"]
 pub struct DropOutdated<A>
@@ -123,6 +124,25 @@ pub fn paged_crates_io_remote_call<T, M, E, Err>(
     merge: M,
     extract: E,
 ) -> Box<dyn futures::Future<Item = T, Error = RemoteCallError> + Send>
+where
+    T: Default + Send + 'static,
+    Err: Error + Send + 'static,
+    M: Fn(T, CallResult) -> Result<T, Err> + Send + Sync + 'static,
+    E: FnOnce(CallResult) -> Result<(CallMetaData, T), Err> + Send + Sync + 'static,
+{
+    paged_crates_io_remote_call_from(url, 1, max_items, session, merge, extract)
+}
+
+/// Like `paged_crates_io_remote_call`, but skipping the items of the pages before `first_page`,
+/// whose size is the smaller one of `max_items` and the maximum page size.
+pub fn paged_crates_io_remote_call_from<T, M, E, Err>(
+    url: &str,
+    first_page: u32,
+    max_items: Option<u32>,
+    session: Arc<Mutex<Session>>,
+    merge: M,
+    extract: E,
+) -> Box<dyn futures::Future<Item = T, Error = RemoteCallError> + Send>
 where
     T: Default + Send + 'static,
     Err: Error + Send + 'static,
@@ -130,34 +150,42 @@ where
     E: FnOnce(CallResult) -> Result<(CallMetaData, T), Err> + Send + Sync + 'static,
 {
     let max_items = max_items.unwrap_or(u32::max_value());
-    let page_size = cmp::min(MAX_ITEMS_PER_PAGE, max_items);
+    let page_size = max_items.clamp(1, MAX_ITEMS_PER_PAGE);
+    let first_page = first_page.max(1);
+    let skipped = (first_page - 1).saturating_mul(page_size);
+    let first_url = if first_page > 1 {
+        format!("{}&page={}&per_page={}", url, first_page, page_size)
+    } else {
+        format!("{}&per_page={}", url, page_size)
+    };
 
     let url = url.to_owned();
-    Box::new(
-        remote_call(&format!("{}&per_page={}", url, page_size), session.clone()).and_then(
-            move |r| {
-                extract(r)
-                    .map_err(|e| RemoteCallError::Any(Box::new(e)))
-                    .into_future()
-                    .and_then(move |(m, initial)| {
-                        let mut f = Vec::new();
-                        let num_chunks = cmp::min(
-                            m.total.saturating_sub(m.items),
-                            max_items.saturating_sub(m.items),
-                        ) / page_size;
-                        let remainder = if m.total % page_size > 0 { 1 } else { 0 };
-                        for ci in 0..num_chunks + remainder {
-                            f.push(remote_call(
-                                &format!("{}&page={}&per_page={}", url, 2 + ci, page_size),
-                                session.clone(),
-                            ));
-                        }
-                        futures::stream::futures_unordered(f.into_iter())
-                            .fold(initial, move |m, r| {
-                                merge(m, r).map_err(|e| RemoteCallError::Any(Box::new(e)))
-                            })
-                    })
-            },
-        ),
-    )
+    Box::new(remote_call(&first_url, session.clone()).and_then(move |r| {
+        extract(r)
+            .map_err(|e| RemoteCallError::Any(Box::new(e)))
+            .into_future()
+            .and_then(move |(m, initial)| {
+                let mut f = Vec::new();
+                let remaining = cmp::min(
+                    m.total.saturating_sub(skipped).saturating_sub(m.items),
+                    max_items.saturating_sub(m.items),
+                );
+                let num_pages =
+                    remaining / page_size + if remaining % page_size > 0 { 1 } else { 0 };
+                for ci in 0..num_pages {
+                    f.push(remote_call(
+                        &format!(
+                            "{}&page={}&per_page={}",
+                            url,
+                            first_page + 1 + ci,
+                            page_size
+                        ),
+                        session.clone(),
+                    ));
+                }
+                futures::stream::futures_ordered(f).fold(initial, move |m, r| {
+                    merge(m, r).map_err(|e| RemoteCallError::Any(Box::new(e)))
+                })
+            })
+    }))
 }
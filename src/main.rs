@@ -1,27 +1,91 @@
 #![deny(unsafe_code)]
 
-#[cfg_attr(any(feature = "recent-changes", feature = "list"), macro_use)]
+#[cfg_attr(
+    any(
+        feature = "recent-changes",
+        feature = "list",
+        feature = "search",
+        feature = "info",
+        feature = "versions",
+        feature = "owners",
+        feature = "downloads"
+    ),
+    macro_use
+)]
 extern crate prettytable;
 #[cfg_attr(
-    any(feature = "list", feature = "recent-changes", feature = "search"),
+    any(
+        feature = "list",
+        feature = "recent-changes",
+        feature = "search",
+        feature = "info",
+        feature = "versions",
+        feature = "owners",
+        feature = "downloads",
+        feature = "add"
+    ),
     macro_use
 )]
 extern crate quick_error;
 
 mod args;
 mod error;
-#[cfg(any(feature = "list", feature = "search"))]
+#[cfg(any(feature = "list", feature = "recent-changes"))]
+mod filter;
+#[cfg(any(
+    feature = "list",
+    feature = "search",
+    feature = "info",
+    feature = "versions",
+    feature = "owners",
+    feature = "downloads",
+    feature = "add"
+))]
 mod http_utils;
+#[cfg(any(feature = "search", feature = "add"))]
+mod manifest;
+#[cfg(any(
+    feature = "list",
+    feature = "recent-changes",
+    feature = "search",
+    feature = "info",
+    feature = "versions",
+    feature = "owners",
+    feature = "downloads"
+))]
+mod output;
 mod scmds;
 mod structs;
+#[cfg(any(
+    feature = "list",
+    feature = "recent-changes",
+    feature = "search",
+    feature = "info",
+    feature = "versions",
+    feature = "owners",
+    feature = "downloads"
+))]
+mod template;
 
 use error::ok_or_exit;
-#[cfg(feature = "search")]
-use scmds::handle_interactive_search;
+#[cfg(feature = "add")]
+use scmds::handle_add;
+#[cfg(feature = "downloads")]
+use scmds::handle_downloads;
+#[cfg(feature = "info")]
+use scmds::handle_info;
+#[cfg(feature = "owners")]
+use scmds::handle_owners;
 #[cfg(feature = "recent-changes")]
 use scmds::handle_recent_changes;
 #[cfg(feature = "list")]
-use scmds::{by_user, handle_list};
+use scmds::{
+    by_category, by_keyword, by_team, by_user, handle_list, reverse_dependencies, Order, Selection,
+};
+#[cfg(feature = "search")]
+use scmds::{handle_interactive_search, handle_search};
+#[cfg(feature = "versions")]
+use scmds::{handle_versions, ExcludeVersions};
 
 use crate::args::Parsed;
 
@@ -30,28 +94,178 @@ fn main() {
     use args::SubCommands::*;
     use clap::Clap;
     let args: Parsed = args::Parsed::parse();
+    #[cfg(any(
+        feature = "list",
+        feature = "search",
+        feature = "info",
+        feature = "versions",
+        feature = "owners",
+        feature = "downloads",
+        feature = "add"
+    ))]
+    let api_url = args.api_url.trim_end_matches('/').to_owned();
 
     match args.sub {
         #[cfg(feature = "recent-changes")]
         Some(RecentChanges {
             repository,
             output_format,
-        }) => ok_or_exit(handle_recent_changes(repository, output_format)),
+            template,
+            columns,
+            filter,
+        }) => ok_or_exit(handle_recent_changes(
+            repository,
+            filter,
+            output::Options {
+                kind: output_format,
+                template,
+                columns,
+            },
+        )),
         #[cfg(feature = "list")]
-        Some(List { cmd, output_format }) => {
+        Some(List {
+            cmd,
+            output_format,
+            template,
+            columns,
+            filter,
+            sort,
+            reverse,
+            limit,
+            offset,
+        }) => {
             use args::ListCmd::*;
+            let selection = Selection {
+                filter,
+                order: Order {
+                    by: sort.unwrap_or(match limit {
+                        Some(_) => args::SortBy::Downloads,
+                        None => args::SortBy::Name,
+                    }),
+                    reverse,
+                },
+                limit,
+                offset,
+            };
+            let options = output::Options {
+                kind: output_format,
+                template,
+                columns,
+            };
             ok_or_exit(match cmd {
-                ByUser { id } => handle_list(output_format, move |session| by_user(id, session)),
+                ByUser { user } => handle_list(options, selection, move |selection, session| {
+                    by_user(user, selection, &api_url, session)
+                }),
+                ByTeam { team } => handle_list(options, selection, move |selection, session| {
+                    by_team(team, selection, &api_url, session)
+                }),
+                ByKeyword { keyword } => {
+                    handle_list(options, selection, move |selection, session| {
+                        by_keyword(&keyword, selection, &api_url, session)
+                    })
+                }
+                ByCategory { category } => {
+                    handle_list(options, selection, move |selection, session| {
+                        by_category(&category, selection, &api_url, session)
+                    })
+                }
+                ReverseDeps { name } => {
+                    handle_list(options, selection, move |selection, session| {
+                        reverse_dependencies(name, selection, &api_url, session)
+                    })
+                }
             })
         }
+        #[cfg(feature = "info")]
+        Some(Info {
+            name,
+            output_format,
+        }) => ok_or_exit(handle_info(name, &api_url, output_format)),
+        #[cfg(feature = "versions")]
+        Some(Versions {
+            name,
+            hide_yanked,
+            hide_prereleases,
+            output_format,
+        }) => ok_or_exit(handle_versions(
+            name,
+            ExcludeVersions {
+                yanked: hide_yanked,
+                prereleases: hide_prereleases,
+            },
+            &api_url,
+            output_format,
+        )),
+        #[cfg(feature = "owners")]
+        Some(Owners {
+            names,
+            output_format,
+        }) => ok_or_exit(handle_owners(names, &api_url, output_format)),
+        #[cfg(feature = "downloads")]
+        Some(Downloads {
+            name,
+            by_version,
+            output_format,
+        }) => ok_or_exit(handle_downloads(name, by_version, &api_url, output_format)),
         #[cfg(feature = "search")]
-        Some(Search) => ok_or_exit(handle_interactive_search()),
+        Some(Search {
+            query,
+            limit,
+            sort,
+            output_format,
+            template,
+            columns,
+            no_history,
+            history_size,
+        }) => {
+            let options = output::Options {
+                kind: output_format,
+                template,
+                columns,
+            };
+            ok_or_exit(match query {
+                Some(query) => handle_search(query, sort, limit, &api_url, options),
+                None => handle_interactive_search(
+                    api_url,
+                    if no_history { Some(0) } else { history_size },
+                    sort,
+                    limit,
+                    options,
+                ),
+            })
+        }
+        #[cfg(feature = "add")]
+        Some(Add {
+            name,
+            dev,
+            build,
+            manifest_path,
+        }) => ok_or_exit(handle_add(
+            name,
+            match (dev, build) {
+                (true, _) => manifest::Section::Dev,
+                (_, true) => manifest::Section::Build,
+                _ => manifest::Section::Normal,
+            },
+            manifest_path,
+            &api_url,
+        )),
         #[cfg(feature = "mine")]
         Some(Criner(args)) => ok_or_exit(criner_cli::run_blocking(args)),
         None =>
         {
             #[cfg(feature = "search")]
-            ok_or_exit(handle_interactive_search())
+            ok_or_exit(handle_interactive_search(
+                api_url,
+                None,
+                args::SearchSort::default(),
+                scmds::DEFAULT_SEARCH_LIMIT,
+                output::Options {
+                    kind: args::OutputKind::human,
+                    template: None,
+                    columns: None,
+                },
+            ))
         }
     }
 }
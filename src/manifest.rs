@@ -0,0 +1,288 @@
+//! Add dependencies to a `Cargo.toml` by editing it as text, which keeps its formatting and comments.
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+quick_error! {
+    #[derive(Debug)]
+    pub enum Error {
+        NotFound(dir: PathBuf) {
+            display("Could not find a Cargo.toml in '{}' or any of its parents", dir.display())
+        }
+        Virtual(path: PathBuf) {
+            display("'{}' has no [package] to add dependencies to, like the root of a workspace", path.display())
+        }
+        AlreadyPresent(name: String, section: Section) {
+            display("'{}' is already in the [{}] section", name, section)
+        }
+        Io(err: io::Error, path: PathBuf) {
+            display("Could not read or write '{}'", path.display())
+            cause(err)
+        }
+    }
+}
+
+/// The table of a manifest to add dependencies to
+#[derive(Debug, Clone, Copy)]
+pub enum Section {
+    Normal,
+    Dev,
+    Build,
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Section::Normal => "dependencies",
+            Section::Dev => "dev-dependencies",
+            Section::Build => "build-dependencies",
+        })
+    }
+}
+
+/// Find the `Cargo.toml` in `dir` or the closest of its parents, which must be the one of a package.
+pub fn find(dir: &Path) -> Result<PathBuf, Error> {
+    let path = dir
+        .ancestors()
+        .map(|dir| dir.join("Cargo.toml"))
+        .find(|path| path.is_file())
+        .ok_or_else(|| Error::NotFound(dir.to_owned()))?;
+    let manifest = fs::read_to_string(&path).map_err(|err| Error::Io(err, path.clone()))?;
+    if has_package(&manifest) {
+        Ok(path)
+    } else {
+        Err(Error::Virtual(path))
+    }
+}
+
+/// True if `manifest` has a `[package]`, which virtual manifests of workspaces don't.
+fn has_package(manifest: &str) -> bool {
+    manifest
+        .lines()
+        .any(|l| table_name(l).as_deref() == Some("package"))
+}
+
+/// The name of the table if `line` is a table header like `[dependencies]`
+fn table_name(line: &str) -> Option<String> {
+    let line = line.split('#').next().unwrap_or_default().trim();
+    if line.starts_with('[') && line.ends_with(']') {
+        Some(
+            line.trim_start_matches('[')
+                .trim_end_matches(']')
+                .split('.')
+                .map(|part| part.trim().trim_matches('"').trim_matches('\''))
+                .collect::<Vec<_>>()
+                .join("."),
+        )
+    } else {
+        None
+    }
+}
+
+/// The key if `line` starts a key-value pair like `name = "1.0"`, or its first part for dotted keys
+/// like `name.version = "1.0"`.
+fn key(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    let (key, _) = line.split_at(line.find('=')?);
+    let key = key.split('.').next().unwrap_or_default();
+    let key = key.trim().trim_matches('"').trim_matches('\'');
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        Some(key)
+    } else {
+        None
+    }
+}
+
+/// How many more brackets and braces `line` opens than it closes, ignoring strings and comments
+fn nesting(line: &str) -> i32 {
+    let (mut depth, mut quote) = (0, None);
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some('"'), '\\') => {
+                chars.next();
+            }
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (None, '#') => break,
+            (None, '[') | (None, '{') => depth += 1,
+            (None, ']') | (None, '}') => depth -= 1,
+            _ => {}
+        }
+    }
+    depth
+}
+
+fn is_content(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty() && !line.starts_with('#')
+}
+
+/// Add `name = "version"` to the `section` of `manifest`, a `Cargo.toml` file.
+///
+/// If the keys of the section are sorted, the new one is sorted in, otherwise it is added after the last one.
+/// A missing section is added to the end.
+pub fn add_dependency(
+    manifest: &str,
+    section: Section,
+    name: &str,
+    version: &str,
+) -> Result<String, Error> {
+    let newline = if manifest.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let entry = format!("{} = \"{}\"", name, version);
+    let mut lines: Vec<&str> = manifest.lines().collect();
+    let table = section.to_string();
+    if lines
+        .iter()
+        .any(|l| table_name(l) == Some(format!("{}.{}", table, name)))
+    {
+        return Err(Error::AlreadyPresent(name.to_owned(), section));
+    }
+
+    let start = match lines
+        .iter()
+        .position(|l| table_name(l).as_deref() == Some(table.as_str()))
+    {
+        Some(header) => header + 1,
+        None => {
+            let mut manifest = manifest.to_owned();
+            if !manifest.is_empty() && !manifest.ends_with('\n') {
+                manifest.push_str(newline);
+            }
+            if lines.last().is_some_and(|l| is_content(l)) {
+                manifest.push_str(newline);
+            }
+            manifest.push_str(&format!("[{}]{}{}{}", table, newline, entry, newline));
+            return Ok(manifest);
+        }
+    };
+    let end = lines[start..]
+        .iter()
+        .position(|l| table_name(l).is_some())
+        .map_or(lines.len(), |p| start + p);
+    let mut depth = 0;
+    let keys: Vec<(usize, &str)> = (start..end)
+        .filter_map(|i| {
+            let key = if depth == 0 { key(lines[i]) } else { None };
+            depth += nesting(lines[i]);
+            key.map(|k| (i, k))
+        })
+        .collect();
+    if keys.iter().any(|(_, k)| *k == name) {
+        return Err(Error::AlreadyPresent(name.to_owned(), section));
+    }
+
+    let sorted = keys.windows(2).all(|w| w[0].1 <= w[1].1);
+    let position = match keys.iter().find(|(_, k)| *k > name) {
+        Some((i, _)) if sorted => (start..*i)
+            .rev()
+            .take_while(|i| lines[*i].trim_start().starts_with('#'))
+            .last()
+            .unwrap_or(*i),
+        _ => (start..end)
+            .rev()
+            .find(|i| is_content(lines[*i]))
+            .map_or(start, |i| i + 1),
+    };
+    lines.insert(position, &entry);
+
+    let mut manifest_out = lines.join(newline);
+    if manifest.ends_with('\n') {
+        manifest_out.push_str(newline);
+    }
+    Ok(manifest_out)
+}
+
+/// Add `name = "version"` to the `section` of the manifest at `path`.
+pub fn add_dependency_to_file(
+    path: &Path,
+    section: Section,
+    name: &str,
+    version: &str,
+) -> Result<(), Error> {
+    let io_error = |err| Error::Io(err, path.to_owned());
+    let manifest = fs::read_to_string(path).map_err(io_error)?;
+    let manifest = add_dependency(&manifest, section, name, version)?;
+    fs::write(path, manifest).map_err(io_error)
+}
+
+#[test]
+fn test_add_dependency() {
+    let add = |manifest: &str, section, name| add_dependency(manifest, section, name, "1.0.0");
+    let manifest = "[package]\nname = \"x\"\n\n[dependencies]\n# http\ncurl = \"0.4\" # comment\nserde = { version = \"1\",\n  features = [\"derive\"] }\n\n# tests\n[dev-dependencies]\n";
+    assert_eq!(
+        add(manifest, Section::Normal, "regex").unwrap(),
+        "[package]\nname = \"x\"\n\n[dependencies]\n# http\ncurl = \"0.4\" # comment\nregex = \"1.0.0\"\nserde = { version = \"1\",\n  features = [\"derive\"] }\n\n# tests\n[dev-dependencies]\n"
+    );
+    assert_eq!(
+        add(manifest, Section::Normal, "zstd").unwrap(),
+        "[package]\nname = \"x\"\n\n[dependencies]\n# http\ncurl = \"0.4\" # comment\nserde = { version = \"1\",\n  features = [\"derive\"] }\nzstd = \"1.0.0\"\n\n# tests\n[dev-dependencies]\n"
+    );
+    assert_eq!(
+        add(manifest, Section::Dev, "a").unwrap(),
+        format!("{}a = \"1.0.0\"\n", manifest)
+    );
+    assert_eq!(
+        add("[package]\r\nname = \"x\"", Section::Build, "cc").unwrap(),
+        "[package]\r\nname = \"x\"\r\n\r\n[build-dependencies]\r\ncc = \"1.0.0\"\r\n"
+    );
+    assert_eq!(
+        add(
+            "[dependencies]\nb = \"1\"\na = \"1\"\n",
+            Section::Normal,
+            "c"
+        )
+        .unwrap(),
+        "[dependencies]\nb = \"1\"\na = \"1\"\nc = \"1.0.0\"\n"
+    );
+    assert!(matches!(
+        add(manifest, Section::Normal, "serde"),
+        Err(Error::AlreadyPresent(..))
+    ));
+    assert!(matches!(
+        add(
+            "[dependencies.serde]\nversion = \"1\"\n",
+            Section::Normal,
+            "serde"
+        ),
+        Err(Error::AlreadyPresent(..))
+    ));
+    assert!(matches!(
+        add(
+            "[dependencies]\nserde.version = \"1\"\nserde.features = [\"derive\"]\n",
+            Section::Normal,
+            "serde"
+        ),
+        Err(Error::AlreadyPresent(..))
+    ));
+    assert_eq!(
+        add(
+            "[dependencies]\ncurl.workspace = true\nserde.workspace = true\n",
+            Section::Normal,
+            "regex"
+        )
+        .unwrap(),
+        "[dependencies]\ncurl.workspace = true\nregex = \"1.0.0\"\nserde.workspace = true\n"
+    );
+}
+
+#[test]
+fn test_has_package() {
+    assert!(has_package("[package]\nname = \"x\"\n"));
+    assert!(has_package(
+        "[workspace]\nmembers = [\"x\"]\n\n[ package ] # the root\n"
+    ));
+    assert!(!has_package(
+        "[workspace]\nmembers = [\"x\"]\n\n[workspace.package]\nversion = \"1\"\n"
+    ));
+}
@@ -0,0 +1,287 @@
+use crate::{
+    args::OutputKind,
+    template::{self, plain, Field, Template},
+};
+use prettytable::{format, Cell, Row, Table};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    io::{self, Write},
+    str::FromStr,
+};
+
+quick_error! {
+    #[derive(Debug)]
+    pub enum Error {
+        Json(err: serde_json::Error) {
+            display("Items could not be serialized")
+            from()
+            cause(err)
+        }
+        Csv(err: csv::Error) {
+            display("Items could not be written as delimiter separated values")
+            from()
+            cause(err)
+        }
+        Yaml(err: serde_yaml::Error) {
+            display("Items could not be serialized as yaml")
+            from()
+            cause(err)
+        }
+        Template(err: template::Error) {
+            display("{}", err)
+            from()
+        }
+        Io(err: io::Error) {
+            display("The output could not be written")
+            from()
+            cause(err)
+        }
+    }
+}
+
+/// An item which can be displayed as row in a table, with the titles depending on all items.
+pub trait ListItem: Serialize + Sized {
+    fn titles(items: &[Self]) -> Row;
+    fn row(&self) -> Row;
+}
+
+/// The fields of records to show as columns of a table, like `name,max_version,repository`
+#[derive(Debug, Clone)]
+pub struct Columns(Vec<Field>);
+
+impl FromStr for Columns {
+    type Err = template::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut position = 0;
+        s.split(',')
+            .map(|name| {
+                let field = name
+                    .parse()
+                    .map_err(|_| template::Error::EmptyField(position));
+                position += name.len() + 1;
+                field
+            })
+            .collect::<Result<_, _>>()
+            .map(Columns)
+    }
+}
+
+impl Columns {
+    fn titles(&self) -> Row {
+        Row::new(
+            self.0
+                .iter()
+                .map(|field| Cell::new(&field.to_string()).style_spec("b"))
+                .collect(),
+        )
+    }
+
+    fn rows<T>(&self, items: &[T]) -> Result<Vec<Row>, Error>
+    where
+        T: Serialize,
+    {
+        items
+            .iter()
+            .map(|item| {
+                let value = serde_json::to_value(item)?;
+                self.0
+                    .iter()
+                    .map(|field| Ok(Cell::new(&plain(field.lookup(&value)?))))
+                    .collect::<Result<_, Error>>()
+                    .map(Row::new)
+            })
+            .collect()
+    }
+}
+
+/// How to present records to the user
+pub struct Options {
+    pub kind: OutputKind,
+    /// If set, it is used instead of `kind`
+    pub template: Option<Template>,
+    /// If set, the columns of tables for humans
+    pub columns: Option<Columns>,
+}
+
+pub fn print_rows(titles: Row, rows: Vec<Row>) {
+    if rows.is_empty() {
+        return;
+    }
+    let mut table = rows.into_iter().fold(Table::new(), |mut t, row| {
+        t.add_row(row);
+        t
+    });
+    table.set_format(*format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    table.set_titles(titles);
+    table.print_tty(false);
+}
+
+pub fn print_table<T>(items: &[T])
+where
+    T: ListItem,
+{
+    print_rows(T::titles(items), items.iter().map(T::row).collect());
+}
+
+fn markdown_line<S: AsRef<str>>(cells: impl IntoIterator<Item = S>) -> String {
+    cells.into_iter().fold(String::from("|"), |mut line, cell| {
+        line.push(' ');
+        line.push_str(&cell.as_ref().replace('|', "\\|").replace('\n', " "));
+        line.push_str(" |");
+        line
+    })
+}
+
+/// Print a table in the github flavor of markdown, with all columns left-aligned.
+pub fn print_markdown(titles: &Row, rows: impl IntoIterator<Item = Row>) -> Result<(), Error> {
+    let out = io::stdout();
+    let mut out = out.lock();
+    writeln!(
+        out,
+        "{}",
+        markdown_line(titles.iter().map(|c| c.get_content()))
+    )?;
+    writeln!(out, "{}", markdown_line(titles.iter().map(|_| "---")))?;
+    for row in rows {
+        writeln!(
+            out,
+            "{}",
+            markdown_line(row.iter().map(|c| c.get_content()))
+        )?;
+    }
+    Ok(())
+}
+
+pub fn print_json<T>(item: &T) -> Result<(), Error>
+where
+    T: Serialize + ?Sized,
+{
+    serde_json::to_writer_pretty(io::stdout(), item).map_err(Into::into)
+}
+
+pub fn print_yaml<T>(item: &T) -> Result<(), Error>
+where
+    T: Serialize + ?Sized,
+{
+    serde_yaml::to_writer(io::stdout(), item)?;
+    writeln!(io::stdout())?;
+    Ok(())
+}
+
+/// Write each item as json object on its own line.
+pub fn print_ndjson<T>(items: &[T]) -> Result<(), Error>
+where
+    T: Serialize,
+{
+    let out = io::stdout();
+    let mut out = out.lock();
+    for item in items {
+        serde_json::to_writer(&mut out, item)?;
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// Write one line per item, and a header with the names of all fields of the first item.
+/// Nested fields are written as json.
+pub fn print_delimited<T>(items: &[T], delimiter: u8) -> Result<(), Error>
+where
+    T: Serialize,
+{
+    let mut out = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(io::stdout());
+    for (index, item) in items.iter().enumerate() {
+        let fields = match serde_json::to_value(item)? {
+            Value::Object(fields) => fields,
+            other => std::iter::once(("value".to_owned(), other)).collect(),
+        };
+        if index == 0 {
+            out.write_record(fields.keys())?;
+        }
+        out.write_record(fields.values().map(plain))?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Print each item on its own line as rendered by the template.
+pub fn print_templated<T>(template: &Template, items: &[T]) -> Result<(), Error>
+where
+    T: Serialize,
+{
+    let out = io::stdout();
+    let mut out = out.lock();
+    for item in items {
+        writeln!(out, "{}", template.render(item)?)?;
+    }
+    Ok(())
+}
+
+/// Print items in one of the formats meant for machines.
+/// The formats meant for humans are to be handled by the caller.
+pub fn print_records<T>(output_format: OutputKind, items: &[T]) -> Result<(), Error>
+where
+    T: Serialize,
+{
+    match output_format {
+        OutputKind::json => print_json(items),
+        OutputKind::ndjson => print_ndjson(items),
+        OutputKind::yaml => print_yaml(items),
+        OutputKind::csv => print_delimited(items, b','),
+        OutputKind::tsv => print_delimited(items, b'\t'),
+        OutputKind::human | OutputKind::markdown => {
+            unreachable!("human readable formats are handled by the caller")
+        }
+    }
+}
+
+pub fn print_items<T>(output_format: OutputKind, items: &[T]) -> Result<(), Error>
+where
+    T: ListItem,
+{
+    match output_format {
+        OutputKind::human => {
+            print_table(items);
+            Ok(())
+        }
+        OutputKind::markdown => print_markdown(&T::titles(items), items.iter().map(T::row)),
+        _ => print_records(output_format, items),
+    }
+}
+
+pub fn print<T>(options: &Options, items: &[T]) -> Result<(), Error>
+where
+    T: ListItem,
+{
+    match (options.kind, &options.template, &options.columns) {
+        (_, Some(template), _) => print_templated(template, items),
+        (OutputKind::human, None, Some(columns)) => {
+            print_rows(columns.titles(), columns.rows(items)?);
+            Ok(())
+        }
+        (OutputKind::markdown, None, Some(columns)) => {
+            print_markdown(&columns.titles(), columns.rows(items)?)
+        }
+        (kind, None, _) => print_items(kind, items),
+    }
+}
+
+#[test]
+fn test_columns() {
+    let items = vec![serde_json::json!({"name": "a", "license": null, "links": {"owners": "/o"}})];
+    let columns: Columns = "links.owners, name,license".parse().unwrap();
+    let rows = columns.rows(&items).unwrap();
+    let cells: Vec<_> = rows[0].iter().map(Cell::get_content).collect();
+    assert_eq!(cells, ["/o", "a", ""]);
+    assert!(matches!(
+        "name,,license".parse::<Columns>(),
+        Err(template::Error::EmptyField(5))
+    ));
+    assert!(matches!(
+        "name,repo".parse::<Columns>().unwrap().rows(&items),
+        Err(Error::Template(_))
+    ));
+}
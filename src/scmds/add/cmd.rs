@@ -0,0 +1,54 @@
+use super::error::Error;
+use crate::{
+    http_utils::{remote_call, CallResult},
+    manifest::{self, Section},
+    structs::{Crate, CrateResponse},
+};
+use futures::Future;
+use std::{
+    env,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+use tokio_core::reactor;
+use tokio_curl::Session;
+
+fn crate_from_callresult(name: &str, c: CallResult) -> Result<Crate, Error> {
+    let (buf, mut easy) = c;
+    if let Ok(404) = easy.response_code() {
+        return Err(Error::UnknownCrate(name.to_owned()));
+    }
+    let CrateResponse { krate, .. } = serde_json::from_slice(&buf.lock().unwrap())?;
+    Ok(krate)
+}
+
+pub fn handle_add(
+    name: String,
+    section: Section,
+    manifest_path: Option<PathBuf>,
+    api_url: &str,
+) -> Result<(), Error> {
+    let manifest_path = match manifest_path {
+        Some(path) => path,
+        None => manifest::find(&env::current_dir().map_err(Error::CurrentDir)?)?,
+    };
+    let mut reactor = reactor::Core::new().map_err(Error::ReactorInit)?;
+    let session = Arc::new(Mutex::new(Session::new(reactor.handle())));
+    let fut = remote_call(
+        &format!("{}/api/v1/crates/{}", api_url, urlencoding::encode(&name)),
+        session,
+    )
+    .map_err(Error::from)
+    .and_then(move |c| crate_from_callresult(&name, c));
+    let krate = reactor.run(fut)?;
+
+    manifest::add_dependency_to_file(&manifest_path, section, &krate.name, &krate.max_version)?;
+    println!(
+        "Added {} = \"{}\" to [{}] of '{}'",
+        krate.name,
+        krate.max_version,
+        section,
+        manifest_path.display()
+    );
+    Ok(())
+}
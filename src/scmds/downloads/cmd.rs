@@ -0,0 +1,268 @@
+use super::error::Error;
+use crate::{
+    args::OutputKind,
+    http_utils::{remote_call, CallResult},
+    output::{print_markdown, print_records, print_rows},
+    structs::{DownloadsResponse, VersionsResponse},
+};
+use futures::Future;
+use prettytable::Row;
+use serde_derive::Serialize;
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+use tokio_core::reactor;
+use tokio_curl::Session;
+
+const MAX_BARS: usize = 60;
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// The downloads of a single day, possibly of a particular version only
+#[derive(Serialize, Debug, PartialEq)]
+pub struct DailyDownloads {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub date: String,
+    pub downloads: i64,
+}
+
+fn downloads_from_callresult_buf(
+    buf: &[u8],
+    version_names: &HashMap<u32, String>,
+) -> Result<Vec<DailyDownloads>, Error> {
+    let DownloadsResponse {
+        version_downloads,
+        meta,
+    } = serde_json::from_slice(buf)?;
+    let mut downloads: Vec<_> = version_downloads
+        .into_iter()
+        .map(|d| DailyDownloads {
+            version: Some(
+                version_names
+                    .get(&d.version)
+                    .cloned()
+                    .unwrap_or_else(|| d.version.to_string()),
+            ),
+            date: d.date,
+            downloads: d.downloads,
+        })
+        .chain(meta.extra_downloads.into_iter().map(|d| DailyDownloads {
+            version: Some("other".into()),
+            date: d.date,
+            downloads: d.downloads,
+        }))
+        .collect();
+    downloads.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.version.cmp(&b.version)));
+    Ok(downloads)
+}
+
+fn check_crate_exists(name: &str, c: CallResult) -> Result<Arc<Mutex<Vec<u8>>>, Error> {
+    let (buf, mut easy) = c;
+    if let Ok(404) = easy.response_code() {
+        return Err(Error::UnknownCrate(name.to_owned()));
+    }
+    Ok(buf)
+}
+
+fn version_names(
+    name: String,
+    api_url: &str,
+    session: Arc<Mutex<Session>>,
+) -> Box<dyn Future<Item = HashMap<u32, String>, Error = Error> + Send> {
+    Box::new(
+        remote_call(
+            &format!(
+                "{}/api/v1/crates/{}/versions",
+                api_url,
+                urlencoding::encode(&name)
+            ),
+            session,
+        )
+        .map_err(Into::into)
+        .and_then(move |c| {
+            let buf = check_crate_exists(&name, c)?;
+            let buf = buf.lock().unwrap();
+            let VersionsResponse { versions } = serde_json::from_slice(&buf)?;
+            Ok(versions.into_iter().map(|v| (v.id, v.num)).collect())
+        }),
+    )
+}
+
+/// Fetch the daily downloads of all versions of the crate with the given name, sorted by date.
+pub fn crate_downloads(
+    name: String,
+    api_url: &str,
+    session: Arc<Mutex<Session>>,
+) -> Box<dyn Future<Item = Vec<DailyDownloads>, Error = Error> + Send> {
+    let downloads = remote_call(
+        &format!(
+            "{}/api/v1/crates/{}/downloads",
+            api_url,
+            urlencoding::encode(&name)
+        ),
+        session.clone(),
+    )
+    .map_err(Error::from);
+    Box::new(
+        downloads
+            .join(version_names(name.clone(), api_url, session))
+            .and_then(move |(c, version_names)| {
+                let buf = check_crate_exists(&name, c)?;
+                let buf = buf.lock().unwrap();
+                downloads_from_callresult_buf(&buf, &version_names)
+            }),
+    )
+}
+
+/// Sum up the downloads of all versions per day.
+fn merge_versions(downloads: Vec<DailyDownloads>) -> Vec<DailyDownloads> {
+    downloads
+        .into_iter()
+        .fold(BTreeMap::<String, i64>::new(), |mut m, d| {
+            *m.entry(d.date).or_default() += d.downloads;
+            m
+        })
+        .into_iter()
+        .map(|(date, downloads)| DailyDownloads {
+            version: None,
+            date,
+            downloads,
+        })
+        .collect()
+}
+
+/// The amount of days since 1970-01-01 for a date like 2020-12-31.
+fn day_number(date: &str) -> Option<i64> {
+    let mut parts = date.get(..10)?.splitn(3, '-').map(str::parse::<i64>);
+    let (y, m, d) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(y)), Some(Ok(m)), Some(Ok(d))) => (y, m, d),
+        _ => return None,
+    };
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let doy = (153 * (m + if m > 2 { -3 } else { 9 }) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    Some(era * 146_097 + doe - 719_468)
+}
+
+fn sparkline(values: &[i64]) -> String {
+    let max = values.iter().cloned().max().unwrap_or_default();
+    values
+        .iter()
+        .map(|&v| {
+            if v <= 0 {
+                ' '
+            } else {
+                let index = v * BARS.len() as i64 / max - 1;
+                BARS[index.max(0) as usize]
+            }
+        })
+        .collect()
+}
+
+/// A table with a sparkline of the downloads per version, or `None` if there were no downloads.
+fn chart(downloads: &[DailyDownloads]) -> Option<(Row, Vec<Row>)> {
+    let days: Vec<_> = downloads
+        .iter()
+        .filter_map(|d| day_number(&d.date).map(|day| (d, day)))
+        .collect();
+    let (first, last) = match (days.first(), days.last()) {
+        (Some((_, first)), Some((_, last))) => (*first, *last),
+        _ => return None,
+    };
+    let (first_date, last_date) = (days[0].0.date.clone(), days[days.len() - 1].0.date.clone());
+    let num_days = (last - first + 1) as usize;
+    let days_per_bar = 1 + (num_days - 1) / MAX_BARS;
+    let num_bars = 1 + (num_days - 1) / days_per_bar;
+
+    let mut series = BTreeMap::<&str, (Vec<i64>, HashMap<i64, i64>)>::new();
+    for (d, day) in days {
+        let (bars, per_day) = series
+            .entry(d.version.as_deref().unwrap_or("all versions"))
+            .or_insert_with(|| (vec![0; num_bars], HashMap::new()));
+        bars[(day - first) as usize / days_per_bar] += d.downloads;
+        *per_day.entry(day).or_default() += d.downloads;
+    }
+    let mut series: Vec<_> = series
+        .into_iter()
+        .map(|(version, (bars, per_day))| {
+            let total: i64 = bars.iter().sum();
+            let max_per_day = per_day.values().cloned().max().unwrap_or_default();
+            (version, total, max_per_day, bars)
+        })
+        .collect();
+    series.sort_by_key(|(_, total, _, _)| Reverse(*total));
+
+    let titles = row![b -> "Version", b -> "Downloads", b -> "Max/Day", b ->
+        format!("{} … {} ({} day{} per bar)", first_date, last_date, days_per_bar,
+            if days_per_bar == 1 { "" } else { "s" })];
+    let rows = series
+        .into_iter()
+        .map(|(version, total, max_per_day, bars)| {
+            row![version, r -> total, r -> max_per_day, sparkline(&bars)]
+        })
+        .collect();
+    Some((titles, rows))
+}
+
+pub fn handle_downloads(
+    name: String,
+    by_version: bool,
+    api_url: &str,
+    output_format: OutputKind,
+) -> Result<(), Error> {
+    let mut reactor = reactor::Core::new().map_err(Error::ReactorInit)?;
+    let session = Arc::new(Mutex::new(Session::new(reactor.handle())));
+    let fut = crate_downloads(name, api_url, session).and_then(move |downloads| {
+        let downloads = if by_version {
+            downloads
+        } else {
+            merge_versions(downloads)
+        };
+        match output_format {
+            OutputKind::human => {
+                if let Some((titles, rows)) = chart(&downloads) {
+                    print_rows(titles, rows);
+                }
+                Ok(())
+            }
+            OutputKind::markdown => match chart(&downloads) {
+                Some((titles, rows)) => print_markdown(&titles, rows).map_err(Into::into),
+                None => Ok(()),
+            },
+            _ => print_records(output_format, &downloads).map_err(Into::into),
+        }
+    });
+    reactor.run(fut)
+}
+
+#[test]
+fn test_downloads_from_callresult() {
+    let buf = include_bytes!("../../../tests/fixtures/crates-io-cli-downloads.json");
+    let names = std::iter::once((300, "4.0.0".to_owned())).collect();
+    let downloads = downloads_from_callresult_buf(buf, &names).unwrap();
+    assert!(downloads.windows(2).all(|w| w[0].date <= w[1].date));
+    assert!(downloads
+        .iter()
+        .any(|d| d.version.as_deref() == Some("4.0.0")));
+    assert!(downloads
+        .iter()
+        .any(|d| d.version.as_deref() == Some("other")));
+
+    let total: i64 = downloads.iter().map(|d| d.downloads).sum();
+    let merged = merge_versions(downloads);
+    assert!(merged.windows(2).all(|w| w[0].date < w[1].date));
+    assert_eq!(merged.iter().map(|d| d.downloads).sum::<i64>(), total);
+}
+
+#[test]
+fn test_day_number_and_sparkline() {
+    assert_eq!(day_number("1970-01-01"), Some(0));
+    assert_eq!(day_number("2020-03-01"), Some(18_322));
+    assert_eq!(day_number("2020-02-28T00:00:00Z"), Some(18_320));
+    assert_eq!(day_number("yesterday"), None);
+    assert_eq!(sparkline(&[0, 1, 4, 8]), " ▁▄█");
+}
@@ -0,0 +1,4 @@
+mod cmd;
+mod error;
+
+pub use self::cmd::*;
@@ -0,0 +1,116 @@
+use super::error::Error;
+use crate::{
+    args::OutputKind,
+    http_utils::{remote_call, CallResult},
+    output::{print_json, print_markdown, print_records, print_yaml},
+    structs::{Crate, CrateResponse},
+};
+use futures::Future;
+use prettytable::{format, Table};
+use std::sync::{Arc, Mutex};
+use tokio_core::reactor;
+use tokio_curl::Session;
+
+fn info_from_callresult_buf(buf: &[u8]) -> Result<Crate, Error> {
+    let CrateResponse { mut krate, versions } = serde_json::from_slice(buf)?;
+    if krate.license.is_none() {
+        krate.license = versions
+            .iter()
+            .find(|v| v.num == krate.max_version)
+            .or_else(|| versions.first())
+            .and_then(|v| v.license.clone());
+    }
+    Ok(krate)
+}
+
+fn info_from_callresult(name: &str, c: CallResult) -> Result<Crate, Error> {
+    let (buf, mut easy) = c;
+    if let Ok(404) = easy.response_code() {
+        return Err(Error::UnknownCrate(name.to_owned()));
+    }
+    let buf = buf.lock().unwrap();
+    info_from_callresult_buf(&buf)
+}
+
+pub fn crate_info(
+    name: String,
+    api_url: &str,
+    session: Arc<Mutex<Session>>,
+) -> Box<dyn Future<Item = Crate, Error = Error> + Send> {
+    Box::new(
+        remote_call(
+            &format!("{}/api/v1/crates/{}", api_url, urlencoding::encode(&name)),
+            session,
+        )
+        .map_err(Into::into)
+        .and_then(move |c| info_from_callresult(&name, c)),
+    )
+}
+
+/// The non-empty fields of `info` with their human-readable names.
+fn fields(c: &Crate) -> Vec<(&'static str, String)> {
+    let join = |items: &Option<Vec<String>>| items.as_ref().map(|v| v.join(", "));
+    let fields = vec![
+        ("Name", Some(c.name.clone())),
+        ("Description", c.description.clone()),
+        ("Homepage", c.homepage.clone()),
+        ("Repository", c.repository.clone()),
+        ("Documentation", c.documentation.clone()),
+        ("License", c.license.clone()),
+        ("Keywords", join(&c.keywords)),
+        ("Categories", join(&c.categories)),
+        ("Created", c.created_at.clone()),
+        ("Updated", c.updated_at.clone()),
+        ("Downloads", Some(c.downloads.to_string())),
+        ("RecentDownloads", c.recent_downloads.map(|d| d.to_string())),
+        ("MaxVersion", Some(c.max_version.clone())),
+        ("MaxStableVersion", c.max_stable_version.clone()),
+        ("NewestVersion", c.newest_version.clone()),
+    ];
+    fields
+        .into_iter()
+        .filter_map(|(key, value)| value.filter(|v| !v.is_empty()).map(|v| (key, v)))
+        .collect()
+}
+
+fn print_info(info: &Crate) {
+    let mut table = fields(info)
+        .into_iter()
+        .fold(Table::new(), |mut t, (key, value)| {
+            t.add_row(row![b -> key, value]);
+            t
+        });
+    table.set_format(*format::consts::FORMAT_CLEAN);
+    table.print_tty(false);
+}
+
+pub fn handle_info(name: String, api_url: &str, output_format: OutputKind) -> Result<(), Error> {
+    let mut reactor = reactor::Core::new().map_err(Error::ReactorInit)?;
+    let session = Arc::new(Mutex::new(Session::new(reactor.handle())));
+    let fut = crate_info(name, api_url, session).and_then(|info| match output_format {
+        OutputKind::human => {
+            print_info(&info);
+            Ok(())
+        }
+        OutputKind::markdown => print_markdown(
+            &row!["Field", "Value"],
+            fields(&info)
+                .into_iter()
+                .map(|(key, value)| row![key, value]),
+        )
+        .map_err(Into::into),
+        OutputKind::json => print_json(&info).map_err(Into::into),
+        OutputKind::yaml => print_yaml(&info).map_err(Into::into),
+        _ => print_records(output_format, &[info]).map_err(Into::into),
+    });
+    reactor.run(fut)
+}
+
+#[test]
+fn test_info_from_callresult() {
+    let buf = include_bytes!("../../../tests/fixtures/crates-io-cli.json");
+    let info = info_from_callresult_buf(buf).unwrap();
+    assert_eq!(info.name, "crates-io-cli");
+    assert_eq!(info.max_stable_version.as_deref(), Some("4.0.0"));
+    assert_eq!(info.license.as_deref(), Some("MIT"));
+}
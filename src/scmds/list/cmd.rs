@@ -1,13 +1,20 @@
 use super::error::Error;
 use crate::{
-    args::OutputKind,
-    http_utils::{paged_crates_io_remote_call, CallMetaData, CallResult},
-    structs::{Crate, Crates, Meta},
+    args::SortBy,
+    filter::Filter,
+    http_utils::{
+        paged_crates_io_remote_call, remote_call, CallMetaData, CallResult, RemoteCallError,
+    },
+    output::{print, ListItem, Options},
+    structs::{
+        Crate, Crates, Meta, ReverseDependencies, ReverseDependency, TeamResponse, UserResponse,
+    },
 };
 use futures::{Future, IntoFuture};
-use prettytable::{format, Table};
+use prettytable::Row;
+use serde::{de::DeserializeOwned, Serialize};
 use std::{
-    io,
+    collections::HashMap,
     sync::{Arc, Mutex},
 };
 use tokio_core::reactor;
@@ -42,67 +49,458 @@ fn crates_extract(c: CallResult) -> Result<(CallMetaData, Vec<Crate>), Error> {
     })
 }
 
+/// Recover our own errors from paged calls, which box them up.
+fn unwrap_list_error(err: RemoteCallError) -> Error {
+    match err {
+        RemoteCallError::Any(err) => match err.downcast::<Error>() {
+            Ok(err) => *err,
+            Err(err) => RemoteCallError::Any(err).into(),
+        },
+        err => err.into(),
+    }
+}
+
+fn reverse_dependencies_from_callresult_buf(
+    buf: &[u8],
+) -> Result<(Vec<ReverseDependency>, Meta), Error> {
+    let ReverseDependencies {
+        dependencies,
+        versions,
+        meta,
+    } = serde_json::from_slice(buf)?;
+    let versions: HashMap<_, _> = versions.into_iter().map(|v| (v.id, v)).collect();
+    let dependents = dependencies
+        .into_iter()
+        .filter_map(|d| {
+            versions.get(&d.version_id).map(|v| ReverseDependency {
+                name: v.krate.clone(),
+                version: v.num.clone(),
+                req: d.req,
+                kind: d.kind,
+                optional: d.optional,
+                downloads: d.downloads,
+            })
+        })
+        .collect();
+    Ok((dependents, meta))
+}
+
+fn reverse_dependencies_merge(
+    mut r: Vec<ReverseDependency>,
+    c: CallResult,
+) -> Result<Vec<ReverseDependency>, Error> {
+    reverse_dependencies_from_callresult_buf(&c.0.lock().unwrap()).map(|(mut res, _)| {
+        r.append(&mut res);
+        r
+    })
+}
+
+fn reverse_dependencies_extract(
+    name: &str,
+    c: CallResult,
+) -> Result<(CallMetaData, Vec<ReverseDependency>), Error> {
+    let (buf, mut easy) = c;
+    if let Ok(404) = easy.response_code() {
+        return Err(Error::UnknownCrate(name.to_owned()));
+    }
+    let (dependents, meta) = reverse_dependencies_from_callresult_buf(&buf.lock().unwrap())?;
+    Ok((
+        CallMetaData {
+            total: meta.total,
+            items: dependents.len() as u32,
+        },
+        dependents,
+    ))
+}
+
+/// Decode the response to a request for the user or team named `login`, or fail with `unknown` if there is none.
+fn login_from_callresult<T>(
+    login: &str,
+    c: CallResult,
+    unknown: fn(String) -> Error,
+) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    let (buf, mut easy) = c;
+    if let Ok(404) = easy.response_code() {
+        return Err(unknown(login.to_owned()));
+    }
+    let response = serde_json::from_slice(&buf.lock().unwrap())?;
+    Ok(response)
+}
+
+fn user_id(
+    user: String,
+    api_url: &str,
+    session: Arc<Mutex<Session>>,
+) -> Box<dyn Future<Item = u32, Error = Error> + Send> {
+    if let Ok(id) = user.parse() {
+        return Box::new(futures::finished(id));
+    }
+    Box::new(
+        remote_call(
+            &format!("{}/api/v1/users/{}", api_url, urlencoding::encode(&user)),
+            session,
+        )
+        .map_err(Into::into)
+        .and_then(move |c| login_from_callresult(&user, c, Error::UnknownUser))
+        .map(|UserResponse { user }| user.id),
+    )
+}
+
+fn team_id(
+    team: String,
+    api_url: &str,
+    session: Arc<Mutex<Session>>,
+) -> Box<dyn Future<Item = u32, Error = Error> + Send> {
+    if let Ok(id) = team.parse() {
+        return Box::new(futures::finished(id));
+    }
+    Box::new(
+        remote_call(
+            &format!("{}/api/v1/teams/{}", api_url, urlencoding::encode(&team)),
+            session,
+        )
+        .map_err(Into::into)
+        .and_then(move |c| login_from_callresult(&team, c, Error::UnknownTeam))
+        .map(|TeamResponse { team }| team.id),
+    )
+}
+
+/// True if `items`, the first ones in the order of the server which is by the descending `by` key, contain
+/// the first `max_items` in our order. This isn't the case if items with the same key as the last one were cut off,
+/// as we order those by name.
+fn contains_first<T>(items: &[T], by: SortBy, max_items: u32) -> bool
+where
+    T: Sortable,
+{
+    if items.len() as u32 <= max_items {
+        return true;
+    }
+    let keys: Vec<_> = items.iter().map(|item| item.sort_key(by)).collect();
+    match keys.iter().min() {
+        Some(smallest @ SortKey::Number(Some(_))) => {
+            !keys.contains(&SortKey::Number(None))
+                && keys.iter().filter(|key| *key > smallest).count() as u32 >= max_items
+        }
+        _ => false,
+    }
+}
+
+/// Fetch `max_items` and one more, to see if items with the same key are cut off.
+/// In that case, or without `max_items`, all items are fetched.
+fn fetch_first<T, F>(
+    max_items: Option<u32>,
+    by: SortBy,
+    fetch: F,
+) -> Box<dyn Future<Item = Vec<T>, Error = Error> + Send>
+where
+    T: Sortable + Send + 'static,
+    F: Fn(Option<u32>) -> Box<dyn Future<Item = Vec<T>, Error = Error> + Send> + Send + 'static,
+{
+    match max_items {
+        Some(max_items) => Box::new(fetch(Some(max_items.saturating_add(1))).and_then(
+            move |items| -> Box<dyn Future<Item = Vec<T>, Error = Error> + Send> {
+                if contains_first(&items, by, max_items) {
+                    Box::new(futures::finished(items))
+                } else {
+                    fetch(None)
+                }
+            },
+        )),
+        None => fetch(None),
+    }
+}
+
+fn crates_by_query(
+    query: String,
+    fetch: Fetch,
+    api_url: &str,
+    session: Arc<Mutex<Session>>,
+) -> Box<dyn Future<Item = Vec<Crate>, Error = Error> + Send> {
+    let url = format!("{}/api/v1/crates?{}&sort={}", api_url, query, fetch.sort);
+    fetch_first(fetch.max_items, fetch.by, move |max_items| {
+        Box::new(
+            paged_crates_io_remote_call(
+                &url,
+                max_items,
+                session.clone(),
+                crates_merge,
+                crates_extract,
+            )
+            .map_err(Into::into),
+        )
+    })
+}
+
 pub fn by_user(
-    id: u32,
+    user: String,
+    selection: &Selection,
+    api_url: &str,
     session: Arc<Mutex<Session>>,
 ) -> Box<dyn Future<Item = Vec<Crate>, Error = Error> + Send> {
+    let api_url = api_url.to_owned();
+    let fetch = selection.crates_fetch();
     Box::new(
-        paged_crates_io_remote_call(
-            &format!(
-                "https://crates.io/api/v1/crates?user_id={}",
-                urlencoding::encode(&format!("{}", id))
-            ),
-            None,
-            session.clone(),
-            crates_merge,
-            crates_extract,
-        )
-        .map_err(Into::into),
+        user_id(user, &api_url, session.clone()).and_then(move |id| {
+            crates_by_query(format!("user_id={}", id), fetch, &api_url, session)
+        }),
     )
 }
 
-pub fn handle_list<F, R>(output_format: OutputKind, do_work: F) -> Result<(), Error>
+pub fn by_team(
+    team: String,
+    selection: &Selection,
+    api_url: &str,
+    session: Arc<Mutex<Session>>,
+) -> Box<dyn Future<Item = Vec<Crate>, Error = Error> + Send> {
+    let api_url = api_url.to_owned();
+    let fetch = selection.crates_fetch();
+    Box::new(
+        team_id(team, &api_url, session.clone()).and_then(move |id| {
+            crates_by_query(format!("team_id={}", id), fetch, &api_url, session)
+        }),
+    )
+}
+
+pub fn by_keyword(
+    keyword: &str,
+    selection: &Selection,
+    api_url: &str,
+    session: Arc<Mutex<Session>>,
+) -> Box<dyn Future<Item = Vec<Crate>, Error = Error> + Send> {
+    crates_by_query(
+        format!("keyword={}", urlencoding::encode(keyword)),
+        selection.crates_fetch(),
+        api_url,
+        session,
+    )
+}
+
+pub fn by_category(
+    category: &str,
+    selection: &Selection,
+    api_url: &str,
+    session: Arc<Mutex<Session>>,
+) -> Box<dyn Future<Item = Vec<Crate>, Error = Error> + Send> {
+    crates_by_query(
+        format!("category={}", urlencoding::encode(category)),
+        selection.crates_fetch(),
+        api_url,
+        session,
+    )
+}
+
+pub fn reverse_dependencies(
+    name: String,
+    selection: &Selection,
+    api_url: &str,
+    session: Arc<Mutex<Session>>,
+) -> Box<dyn Future<Item = Vec<ReverseDependency>, Error = Error> + Send> {
+    let url = format!(
+        "{}/api/v1/crates/{}/reverse_dependencies?",
+        api_url,
+        urlencoding::encode(&name)
+    );
+    fetch_first(
+        selection.max_items(SortBy::Downloads),
+        SortBy::Downloads,
+        move |max_items| {
+            let name = name.clone();
+            Box::new(
+                paged_crates_io_remote_call(
+                    &url,
+                    max_items,
+                    session.clone(),
+                    reverse_dependencies_merge,
+                    move |c| reverse_dependencies_extract(&name, c),
+                )
+                .map_err(unwrap_list_error),
+            )
+        },
+    )
+}
+
+impl ListItem for ReverseDependency {
+    fn titles(items: &[Self]) -> Row {
+        let total: i64 = items.iter().map(|d| d.downloads).sum();
+        row![b -> "Name", b -> "Version", b -> "Requirement", b -> "Kind", b ->
+            format!("Downloads (total={})", total)]
+    }
+
+    fn row(&self) -> Row {
+        row![self.name, self.version, self.req, self.kind, self.downloads]
+    }
+}
+
+/// A value to order items by, with missing values coming first
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+pub enum SortKey<'a> {
+    Number(Option<i64>),
+    Text(Option<&'a str>),
+}
+
+/// Items which can be listed in an `Order`
+pub trait Sortable {
+    /// The orders which apply to this kind of item
+    const ORDERS: &'static [SortBy];
+
+    /// The value to order by, with `by` being one of `ORDERS`
+    fn sort_key(&self, by: SortBy) -> SortKey<'_>;
+    fn name(&self) -> &str;
+}
+
+impl Sortable for Crate {
+    const ORDERS: &'static [SortBy] = &[
+        SortBy::Name,
+        SortBy::Downloads,
+        SortBy::RecentDownloads,
+        SortBy::Created,
+        SortBy::Updated,
+    ];
+
+    fn sort_key(&self, by: SortBy) -> SortKey<'_> {
+        match by {
+            SortBy::Name => SortKey::Text(Some(&self.name)),
+            SortBy::Downloads => SortKey::Number(Some(self.downloads)),
+            SortBy::RecentDownloads => SortKey::Number(self.recent_downloads),
+            SortBy::Created => SortKey::Text(self.created_at.as_deref()),
+            SortBy::Updated => SortKey::Text(self.updated_at.as_deref()),
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Sortable for ReverseDependency {
+    const ORDERS: &'static [SortBy] = &[SortBy::Name, SortBy::Downloads];
+
+    fn sort_key(&self, by: SortBy) -> SortKey<'_> {
+        match by {
+            SortBy::Downloads => SortKey::Number(Some(self.downloads)),
+            _ => SortKey::Text(Some(&self.name)),
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// The order in which to list items
+pub struct Order {
+    pub by: SortBy,
+    pub reverse: bool,
+}
+
+impl Order {
+    /// Sort `items` by our key, using the name to break ties so the order is always the same.
+    fn sort<T>(&self, mut items: Vec<T>) -> Vec<T>
+    where
+        T: Sortable,
+    {
+        let largest_first = !matches!(self.by, SortBy::Name);
+        items.sort_by(|a, b| {
+            let by_key = a.sort_key(self.by).cmp(&b.sort_key(self.by));
+            if largest_first {
+                by_key.reverse()
+            } else {
+                by_key
+            }
+            .then_with(|| a.name().cmp(b.name()))
+        });
+        if self.reverse {
+            items.reverse();
+        }
+        items
+    }
+}
+
+/// Which items to list, and in which order
+pub struct Selection {
+    pub filter: Option<Filter>,
+    pub order: Order,
+    /// The amount of items to show at most
+    pub limit: Option<u32>,
+    /// The amount of items to skip
+    pub offset: u32,
+}
+
+/// What to ask the server for when fetching crates
+struct Fetch {
+    sort: &'static str,
+    by: SortBy,
+    max_items: Option<u32>,
+}
+
+impl Selection {
+    /// The amount of items to fetch if the server returns them in the `server_order`,
+    /// or `None` if all of them are needed to find the ones to show.
+    ///
+    /// Only download counts are ordered by the server like we do, as it compares names and dates differently.
+    fn max_items(&self, server_order: SortBy) -> Option<u32> {
+        match (&self.filter, self.order.reverse, self.limit, self.order.by) {
+            (None, false, Some(limit), SortBy::Downloads)
+            | (None, false, Some(limit), SortBy::RecentDownloads)
+                if server_order == self.order.by =>
+            {
+                Some(self.offset.saturating_add(limit))
+            }
+            _ => None,
+        }
+    }
+
+    /// Let the server sort crates like we do, so only the ones we show have to be fetched.
+    fn crates_fetch(&self) -> Fetch {
+        let sort = match self.order.by {
+            SortBy::Name => "alpha",
+            SortBy::Downloads => "downloads",
+            SortBy::RecentDownloads => "recent-downloads",
+            SortBy::Created => "new",
+            SortBy::Updated => "recent-updates",
+        };
+        Fetch {
+            sort,
+            by: self.order.by,
+            max_items: self.max_items(self.order.by),
+        }
+    }
+
+    fn apply<T>(&self, items: Vec<T>) -> Result<Vec<T>, Error>
+    where
+        T: Serialize + Sortable,
+    {
+        let items = match &self.filter {
+            Some(filter) => filter.retain(items)?,
+            None => items,
+        };
+        let items = self.order.sort(items);
+        Ok(items
+            .into_iter()
+            .skip(self.offset as usize)
+            .take(self.limit.map_or(usize::MAX, |l| l as usize))
+            .collect())
+    }
+}
+
+pub fn handle_list<F, R, T>(options: Options, selection: Selection, do_work: F) -> Result<(), Error>
 where
-    F: FnOnce(Arc<Mutex<Session>>) -> R,
-    R: IntoFuture<Item = Vec<Crate>, Error = Error>,
+    F: FnOnce(&Selection, Arc<Mutex<Session>>) -> R,
+    R: IntoFuture<Item = Vec<T>, Error = Error>,
+    T: ListItem + Sortable,
 {
+    if !T::ORDERS.contains(&selection.order.by) {
+        return Err(Error::UnsupportedOrder(selection.order.by, T::ORDERS));
+    }
     let mut reactor = reactor::Core::new().map_err(Error::ReactorInit)?;
     let session = Arc::new(Mutex::new(Session::new(reactor.handle())));
-    let fut = do_work(session.clone())
+    let fut = do_work(&selection, session.clone())
         .into_future()
-        .and_then(|crates: Vec<Crate>| match output_format {
-            OutputKind::human => {
-                if crates.is_empty() {
-                    return Ok(());
-                }
-                let (mut table, titles) = {
-                    let mut t = Table::new();
-                    t.set_format(*format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
-                    let mut total = 0;
-                    let t = crates.into_iter().fold(t, |mut t, c| {
-                        total += c.downloads;
-                        t.add_row(row![
-                            c.name,
-                            c.description.unwrap_or_default(),
-                            c.downloads,
-                            c.max_version
-                        ]);
-                        t
-                    });
-                    (
-                        t,
-                        row![b -> "Name", b -> "Description", b ->
-                            format!("Downloads (total={})" , total), b -> "MaxVersion"],
-                    )
-                };
-                table.set_titles(titles);
-                table.print_tty(false);
-                Ok(())
-            }
-            OutputKind::json => {
-                serde_json::to_writer_pretty(io::stdout(), &crates).map_err(Into::into)
-            }
-        });
+        .and_then(|items: Vec<T>| selection.apply(items))
+        .and_then(|items| print(&options, &items).map_err(Into::into));
     reactor.run(fut)
 }
 
@@ -112,4 +510,149 @@ fn test_crates_from_callresult() {
     let (crates, meta) = crates_from_callresult_buf(buf).unwrap();
     assert_eq!(meta.total, 244);
     assert_eq!(crates.len(), 10);
+    let krate = &crates[0];
+    assert_eq!(krate.license.as_deref(), Some("MIT"));
+    assert_eq!(krate.created_at.as_deref(), Some("2016-12-25T20:53:32Z"));
+    assert_eq!(
+        krate.links.as_ref().and_then(|l| l.owners.as_deref()),
+        Some("/api/v1/crates/crates-index-diff/owners")
+    );
+}
+
+#[test]
+fn test_reverse_dependencies_from_callresult() {
+    let buf = include_bytes!("../../../tests/fixtures/reverse-dependencies.json");
+    let (dependents, meta) = reverse_dependencies_from_callresult_buf(buf).unwrap();
+    assert_eq!(meta.total, 2);
+    assert_eq!(dependents.len(), 2);
+    assert_eq!(dependents[1].name, "criner");
+    assert_eq!(dependents[1].req, "^6");
+}
+
+#[test]
+fn test_order_sort() {
+    let buf = include_bytes!("../../../tests/fixtures/byrons-crates.json");
+    let (crates, _) = crates_from_callresult_buf(buf).unwrap();
+    let names = |by, reverse| {
+        Order { by, reverse }
+            .sort(crates.clone())
+            .into_iter()
+            .map(|c| c.name)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        names(SortBy::Name, false)[..2],
+        ["crates-index-diff", "crates-io-cli"]
+    );
+    assert_eq!(
+        names(SortBy::Downloads, false)[0],
+        "google-admin1_directory"
+    );
+    assert_eq!(
+        names(SortBy::Downloads, true)[..3],
+        [
+            "crates-io-cli",
+            "google-adexchangeseller2-cli",
+            "google-adexchangebuyer1d4-cli"
+        ]
+    );
+}
+
+#[test]
+fn test_selection_apply() {
+    let buf = include_bytes!("../../../tests/fixtures/byrons-crates.json");
+    let (crates, _) = crates_from_callresult_buf(buf).unwrap();
+    let selection = |by, limit, filter: Option<&str>| Selection {
+        filter: filter.map(|f| f.parse().unwrap()),
+        order: Order { by, reverse: false },
+        limit,
+        offset: 1,
+    };
+    let names = selection(SortBy::Name, Some(2), None)
+        .apply(crates.clone())
+        .unwrap()
+        .into_iter()
+        .map(|c| c.name)
+        .collect::<Vec<_>>();
+    assert_eq!(names.len(), 2);
+    assert_eq!(names[0], "crates-io-cli");
+    let downloads = selection(SortBy::Downloads, Some(2), None);
+    assert_eq!(downloads.max_items(SortBy::Downloads), Some(3));
+    assert_eq!(downloads.max_items(SortBy::Name), None);
+    assert_eq!(
+        selection(SortBy::Downloads, Some(2), Some("downloads > 0")).max_items(SortBy::Downloads),
+        None
+    );
+    assert_eq!(
+        selection(SortBy::Downloads, None, None).max_items(SortBy::Downloads),
+        None
+    );
+}
+
+#[test]
+fn test_server_order_differs() {
+    let krate = |name: &str, downloads| Crate {
+        name: name.into(),
+        downloads,
+        ..Default::default()
+    };
+    // The server sorts names ignoring case, we don't, so all of them are needed.
+    let by_name = Selection {
+        filter: None,
+        order: Order {
+            by: SortBy::Name,
+            reverse: false,
+        },
+        limit: Some(1),
+        offset: 0,
+    };
+    assert_eq!(by_name.max_items(SortBy::Name), None);
+    let first = by_name
+        .apply(vec![krate("a", 1), krate("Inflector", 1)])
+        .unwrap();
+    assert_eq!(first[0].name, "Inflector");
+
+    // We break ties by name, the server doesn't, so those cut off are needed as well.
+    let server_order = [krate("x", 10), krate("c", 5), krate("b", 5), krate("a", 5)];
+    assert!(!contains_first(&server_order[..3], SortBy::Downloads, 2));
+    assert!(contains_first(&server_order[..3], SortBy::Downloads, 1));
+    assert!(contains_first(&server_order[..2], SortBy::Downloads, 2));
+    let recent = |name: &str| Crate {
+        recent_downloads: None,
+        ..krate(name, 1)
+    };
+    assert!(!contains_first(
+        &[recent("a"), recent("b")],
+        SortBy::RecentDownloads,
+        1
+    ));
+}
+
+#[test]
+fn test_unsupported_order() {
+    let options = Options {
+        kind: crate::args::OutputKind::json,
+        template: None,
+        columns: None,
+    };
+    let selection = Selection {
+        filter: None,
+        order: Order {
+            by: SortBy::Updated,
+            reverse: false,
+        },
+        limit: None,
+        offset: 0,
+    };
+    let res = handle_list(
+        options,
+        selection,
+        |_, _| -> Result<Vec<ReverseDependency>, Error> {
+            unreachable!("nothing is fetched if the order does not apply")
+        },
+    );
+    assert!(matches!(
+        res,
+        Err(Error::UnsupportedOrder(SortBy::Updated, _))
+    ));
 }
@@ -1,4 +1,4 @@
-use crate::http_utils::RemoteCallError;
+use crate::{args::SortBy, http_utils::RemoteCallError};
 use std::io;
 
 quick_error! {
@@ -14,9 +14,29 @@ quick_error! {
             from()
             cause(err)
         }
+        UnknownUser(login: String) {
+            display("There is no user with login '{}'", login)
+        }
+        UnknownTeam(login: String) {
+            display("There is no team with login '{}'", login)
+        }
+        UnknownCrate(name: String) {
+            display("There is no crate named '{}'", name)
+        }
         ReactorInit(err: io::Error) {
             description("Could not initialize tokio event loop in worker thread")
             cause(err)
         }
+        UnsupportedOrder(by: SortBy, supported: &'static [SortBy]) {
+            display("Cannot sort by {}, only by {}", by, supported.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))
+        }
+        Filter(err: crate::filter::Error) {
+            display("{}", err)
+            from()
+        }
+        Output(err: crate::output::Error) {
+            display("{}", err)
+            from()
+        }
     }
 }
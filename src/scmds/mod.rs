@@ -1,13 +1,39 @@
+#[cfg(feature = "add")]
+mod add;
+#[cfg(feature = "downloads")]
+mod downloads;
+#[cfg(feature = "info")]
+mod info;
 #[cfg(feature = "list")]
 mod list;
+#[cfg(feature = "owners")]
+mod owners;
 #[cfg(feature = "recent-changes")]
 mod recents;
 #[cfg(feature = "search")]
 mod search;
+#[cfg(feature = "versions")]
+mod versions;
 
+#[cfg(feature = "add")]
+pub use self::add::handle_add;
+#[cfg(feature = "downloads")]
+pub use self::downloads::handle_downloads;
+#[cfg(feature = "info")]
+pub use self::info::handle_info;
 #[cfg(feature = "list")]
-pub use self::list::{by_user, handle_list, Error as ListError};
+pub use self::list::{
+    by_category, by_keyword, by_team, by_user, handle_list, reverse_dependencies,
+    Error as ListError, Order, Selection,
+};
+#[cfg(feature = "owners")]
+pub use self::owners::handle_owners;
 #[cfg(feature = "recent-changes")]
 pub use self::recents::{handle_recent_changes, Error as RecentChangesError};
 #[cfg(feature = "search")]
-pub use self::search::{handle_interactive_search, Error as SearchError};
+pub use self::search::{
+    handle_interactive_search, handle_search, Error as SearchError,
+    DEFAULT_LIMIT as DEFAULT_SEARCH_LIMIT,
+};
+#[cfg(feature = "versions")]
+pub use self::versions::{handle_versions, Exclude as ExcludeVersions};
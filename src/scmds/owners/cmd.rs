@@ -0,0 +1,126 @@
+use super::error::Error;
+use crate::{
+    args::OutputKind,
+    http_utils::{remote_call, CallResult},
+    output::{print_items, ListItem},
+    structs::{Owner, OwnersResponse},
+};
+use futures::{future, Future};
+use prettytable::Row;
+use serde_derive::Serialize;
+use std::{
+    io::{self, BufRead},
+    sync::{Arc, Mutex},
+};
+use tokio_core::reactor;
+use tokio_curl::Session;
+
+/// A user or team owning a crate
+#[derive(Serialize)]
+pub struct CrateOwner {
+    #[serde(rename = "crate")]
+    pub krate: String,
+    #[serde(flatten)]
+    pub owner: Owner,
+}
+
+impl ListItem for CrateOwner {
+    fn titles(_items: &[Self]) -> Row {
+        row![b -> "Crate", b -> "Login", b -> "Kind", b -> "Name"]
+    }
+
+    fn row(&self) -> Row {
+        row![
+            self.krate,
+            self.owner.login,
+            self.owner.kind,
+            self.owner.name.as_deref().unwrap_or_default()
+        ]
+    }
+}
+
+fn owners_from_callresult_buf(buf: &[u8]) -> Result<Vec<Owner>, Error> {
+    let OwnersResponse { users } = serde_json::from_slice(buf)?;
+    Ok(users)
+}
+
+fn owners_from_callresult(name: &str, c: CallResult) -> Result<Vec<Owner>, Error> {
+    let (buf, mut easy) = c;
+    if let Ok(404) = easy.response_code() {
+        return Err(Error::UnknownCrate(name.to_owned()));
+    }
+    let buf = buf.lock().unwrap();
+    owners_from_callresult_buf(&buf)
+}
+
+pub fn crate_owners(
+    name: String,
+    api_url: &str,
+    session: Arc<Mutex<Session>>,
+) -> Box<dyn Future<Item = Vec<CrateOwner>, Error = Error> + Send> {
+    Box::new(
+        remote_call(
+            &format!(
+                "{}/api/v1/crates/{}/owners",
+                api_url,
+                urlencoding::encode(&name)
+            ),
+            session,
+        )
+        .map_err(Into::into)
+        .and_then(move |c| {
+            owners_from_callresult(&name, c).map(|owners| {
+                owners
+                    .into_iter()
+                    .map(|owner| CrateOwner {
+                        krate: name.clone(),
+                        owner,
+                    })
+                    .collect()
+            })
+        }),
+    )
+}
+
+fn names_from_stdin() -> Result<Vec<String>, Error> {
+    let stdin = io::stdin();
+    let mut names = Vec::new();
+    for line in stdin.lock().lines() {
+        let line = line.map_err(Error::ReadNames)?;
+        names.extend(line.split_whitespace().map(ToOwned::to_owned));
+    }
+    Ok(names)
+}
+
+pub fn handle_owners(
+    names: Vec<String>,
+    api_url: &str,
+    output_format: OutputKind,
+) -> Result<(), Error> {
+    let names = if names.is_empty() {
+        names_from_stdin()?
+    } else {
+        names
+    };
+    let mut reactor = reactor::Core::new().map_err(Error::ReactorInit)?;
+    let session = Arc::new(Mutex::new(Session::new(reactor.handle())));
+    let fut = future::join_all(
+        names
+            .into_iter()
+            .map(|name| crate_owners(name, api_url, session.clone())),
+    )
+    .and_then(|owners_per_crate| {
+        let owners: Vec<_> = owners_per_crate.into_iter().flatten().collect();
+        print_items(output_format, &owners).map_err(Into::into)
+    });
+    reactor.run(fut)
+}
+
+#[test]
+fn test_owners_from_callresult() {
+    let buf = include_bytes!("../../../tests/fixtures/crates-io-cli-owners.json");
+    let owners = owners_from_callresult_buf(buf).unwrap();
+    assert_eq!(owners.len(), 2);
+    assert_eq!(owners[0].kind, "user");
+    assert_eq!(owners[1].login, "github:org:team");
+}
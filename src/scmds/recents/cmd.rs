@@ -1,43 +1,34 @@
 use super::error::Error;
-use crate::args::OutputKind;
-use std::{
-    env,
-    io::{self, Write},
-    ops::Add,
-    path::PathBuf,
-    time::Duration,
+use crate::{
+    filter::Filter,
+    output::{print, ListItem, Options},
 };
+use std::{env, io::Write, ops::Add, path::PathBuf, time::Duration};
 
-use crates_index_diff::Index;
+use crates_index_diff::{CrateVersion, Index};
 use parking_lot::{Condvar, Mutex};
-use prettytable::{format, Table};
+use prettytable::Row;
 
-fn show_changes(repo_path: PathBuf, output_kind: OutputKind) -> Result<(), Error> {
+impl ListItem for CrateVersion {
+    fn titles(_items: &[Self]) -> Row {
+        row![b -> "Name", b -> "Version", b -> "Kind"]
+    }
+
+    fn row(&self) -> Row {
+        row![self.name, self.version, self.kind]
+    }
+}
+
+fn show_changes(repo_path: PathBuf, filter: Option<Filter>, options: Options) -> Result<(), Error> {
     std::fs::create_dir_all(&repo_path)
         .map_err(|e| Error::RepositoryDirectory(e, repo_path.clone().into()))?;
     let index = Index::from_path_or_cloned(repo_path)?;
-    let changes = index.fetch_changes()?;
+    let changes = match filter {
+        Some(filter) => filter.retain(index.fetch_changes()?)?,
+        None => index.fetch_changes()?,
+    };
 
-    match output_kind {
-        OutputKind::human => {
-            if !changes.is_empty() {
-                let table = {
-                    let mut t = Table::new();
-                    t.set_titles(row![b -> "Name", b -> "Version", b -> "Kind"]);
-                    t.set_format(*format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
-                    changes.iter().fold(t, |mut t, c| {
-                        t.add_row(row![c.name, c.version, c.kind]);
-                        t
-                    })
-                };
-                table.print_tty(false);
-            }
-            Ok(())
-        }
-        OutputKind::json => {
-            serde_json::to_writer_pretty(io::stdout(), &changes).map_err(Into::into)
-        }
-    }
+    print(&options, &changes).map_err(Into::into)
 }
 
 fn default_repository_dir() -> PathBuf {
@@ -64,12 +55,13 @@ fn message_after_timeout(msg: String, d: Duration) {
 
 pub fn handle_recent_changes(
     repo_path: Option<PathBuf>,
-    output_format: OutputKind,
+    filter: Option<Filter>,
+    options: Options,
 ) -> Result<(), Error> {
     let repo_path = repo_path.unwrap_or_else(default_repository_dir);
     let computation = std::thread::spawn({
         let repo_path = repo_path.clone();
-        move || show_changes(repo_path, output_format)
+        move || show_changes(repo_path, filter, options)
     });
     message_after_timeout(
         format!(
@@ -23,5 +23,13 @@ quick_error! {
             from()
             cause(err)
         }
+        Filter(err: crate::filter::Error) {
+            display("{}", err)
+            from()
+        }
+        Output(err: crate::output::Error) {
+            display("{}", err)
+            from()
+        }
     }
 }
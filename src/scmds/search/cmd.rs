@@ -0,0 +1,90 @@
+use super::{
+    error::Error,
+    interactive::{extract, merge},
+};
+use crate::{
+    args::SearchSort,
+    http_utils::paged_crates_io_remote_call,
+    output::{print, Options},
+};
+use futures::Future;
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+use tokio_core::reactor::Core;
+use tokio_curl::Session;
+
+/// The url to search crates matching `term` with, to which paging parameters can be appended.
+pub fn search_url(api_url: &str, term: &str, sort: SearchSort) -> String {
+    format!(
+        "{}/api/v1/crates?q={}&sort={}",
+        api_url,
+        urlencoding::encode(term),
+        match sort {
+            SearchSort::Relevance => "",
+            SearchSort::Downloads => "downloads",
+            SearchSort::RecentDownloads => "recent-downloads",
+            SearchSort::RecentUpdates => "recent-updates",
+            SearchSort::New => "new",
+        }
+    )
+}
+
+pub fn handle_search(
+    query: String,
+    sort: SearchSort,
+    limit: u32,
+    api_url: &str,
+    options: Options,
+) -> Result<(), Error> {
+    let mut reactor = Core::new().map_err(Error::ReactorInit)?;
+    let session = Arc::new(Mutex::new(Session::new(reactor.handle())));
+    let fut = paged_crates_io_remote_call(
+        &search_url(api_url, &query, sort),
+        Some(limit),
+        session,
+        merge,
+        extract,
+    )
+    .map_err(Error::from)
+    .and_then(move |result| {
+        let mut crates = result.crates;
+        crates.truncate(limit as usize);
+        print(&options, &crates).map_err(Into::into)
+    });
+    reactor.run(fut)
+}
+
+/// Print the results for the search term on the first line of standard input, for when there is no terminal
+/// to search interactively in.
+pub fn handle_piped_search(
+    sort: SearchSort,
+    limit: u32,
+    api_url: &str,
+    options: Options,
+) -> Result<(), Error> {
+    let mut query = String::new();
+    if !termion::is_tty(&io::stdin()) {
+        io::stdin()
+            .read_line(&mut query)
+            .map_err(Error::ReadQuery)?;
+    }
+    let query = query.trim();
+    if query.is_empty() {
+        return Err(Error::NotATerminal);
+    }
+    handle_search(query.to_owned(), sort, limit, api_url, options)
+}
+
+#[test]
+fn test_search_url() {
+    assert_eq!(
+        search_url("https://crates.io", "serde json", SearchSort::Relevance),
+        "https://crates.io/api/v1/crates?q=serde%20json&sort="
+    );
+    assert_eq!(
+        search_url("http://localhost", "a&b", SearchSort::RecentDownloads),
+        "http://localhost/api/v1/crates?q=a%26b&sort=recent-downloads"
+    );
+}
@@ -39,6 +39,17 @@ quick_error! {
             description("The first write to the output channel failed")
             cause(err)
         }
+        NotATerminal {
+            display("Interactive search needs a terminal - pass a search term like 'crates search serde', or pipe one into standard input, to print the results instead")
+        }
+        ReadQuery(err: io::Error) {
+            description("The search term could not be read from standard input")
+            cause(err)
+        }
+        Output(err: crate::output::Error) {
+            display("{}", err)
+            from()
+        }
         MissingRawTerminal (err: io::Error) {
             description("Standard output could not be put into raw mode")
             cause(err)
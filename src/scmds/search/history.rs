@@ -0,0 +1,149 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The amount of search terms to remember if not configured otherwise
+pub const DEFAULT_MAX_ENTRIES: usize = 1000;
+
+/// Search terms of past sessions, oldest first, which can be browsed and searched
+#[derive(Default)]
+pub struct History {
+    entries: Vec<String>,
+    path: Option<PathBuf>,
+    max_entries: usize,
+    /// The index of the entry currently shown, if any
+    position: Option<usize>,
+    /// What to look for in a reverse search, if one is going on
+    query: Option<String>,
+}
+
+/// The file to keep the history in, inside of the data directory of the user.
+fn default_path() -> Option<PathBuf> {
+    env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local").join("share")))
+        .map(|dir| dir.join("crates-io-cli").join("search-history"))
+}
+
+impl History {
+    /// Load the history from the data directory, keeping at most `max_entries`.
+    /// With `max_entries` being 0, nothing is loaded or saved.
+    pub fn load(max_entries: usize) -> History {
+        let path = match default_path() {
+            Some(path) if max_entries > 0 => path,
+            _ => return History::default(),
+        };
+        let entries = fs::read_to_string(&path)
+            .map(|history| history.lines().map(ToOwned::to_owned).collect())
+            .unwrap_or_default();
+        History {
+            path: Some(path),
+            ..History::with_entries(entries, max_entries)
+        }
+    }
+
+    fn with_entries(mut entries: Vec<String>, max_entries: usize) -> History {
+        let excess = entries.len().saturating_sub(max_entries);
+        entries.drain(..excess);
+        History {
+            entries,
+            max_entries,
+            ..Default::default()
+        }
+    }
+
+    /// Remember `term` as the most recent entry, and save the history if it is kept in a file.
+    pub fn add(&mut self, term: &str) -> io::Result<()> {
+        self.reset();
+        let term = term.trim();
+        if term.is_empty() || self.max_entries == 0 {
+            return Ok(());
+        }
+        self.entries.retain(|entry| entry != term);
+        self.entries.push(term.to_owned());
+        let excess = self.entries.len().saturating_sub(self.max_entries);
+        self.entries.drain(..excess);
+        match self.path {
+            Some(ref path) => {
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                let mut history = self.entries.join("\n");
+                history.push('\n');
+                fs::write(path, history)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Stop browsing or searching the history.
+    pub fn reset(&mut self) {
+        self.position = None;
+        self.query = None;
+    }
+
+    /// The entry before the one currently shown, or the most recent one.
+    pub fn previous(&mut self) -> Option<&str> {
+        let position = match self.position {
+            Some(0) => 0,
+            Some(position) => position - 1,
+            None => self.entries.len().checked_sub(1)?,
+        };
+        self.position = Some(position);
+        self.entries.get(position).map(String::as_str)
+    }
+
+    /// The entry after the one currently shown, or an empty term after the most recent one.
+    pub fn next(&mut self) -> Option<&str> {
+        let position = self.position? + 1;
+        if position < self.entries.len() {
+            self.position = Some(position);
+            self.entries.get(position).map(String::as_str)
+        } else {
+            self.reset();
+            Some("")
+        }
+    }
+
+    /// The most recent entry containing `term` which is older than the one currently shown.
+    /// When searching again, `term` is ignored in favor of what was searched for first.
+    pub fn search_backward(&mut self, term: &str) -> Option<&str> {
+        let end = self.position.unwrap_or(self.entries.len());
+        let query = self.query.get_or_insert_with(|| term.to_owned());
+        let position = self.entries[..end]
+            .iter()
+            .rposition(|entry| entry.contains(query.as_str()))?;
+        self.position = Some(position);
+        self.entries.get(position).map(String::as_str)
+    }
+}
+
+#[test]
+fn test_history() {
+    let entries = |e: &[&str]| e.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    let mut history = History::with_entries(entries(&["old", "serde", "json", "toml"]), 3);
+    assert_eq!(history.entries, ["serde", "json", "toml"]);
+
+    history.add(" serde ").unwrap();
+    assert_eq!(history.entries, ["json", "toml", "serde"]);
+    history.add("").unwrap();
+    assert_eq!(history.entries.len(), 3);
+
+    assert_eq!(history.previous(), Some("serde"));
+    assert_eq!(history.previous(), Some("toml"));
+    assert_eq!(history.next(), Some("serde"));
+    assert_eq!(history.next(), Some(""));
+    assert_eq!(history.next(), None);
+
+    assert_eq!(history.search_backward("o"), Some("toml"));
+    assert_eq!(history.search_backward("toml"), Some("json"));
+    assert_eq!(history.search_backward("toml"), None);
+    history.reset();
+    assert_eq!(history.search_backward("x"), None);
+
+    let mut disabled = History::with_entries(Vec::new(), 0);
+    disabled.add("serde").unwrap();
+    assert_eq!(disabled.previous(), None);
+}
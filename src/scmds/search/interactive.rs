@@ -1,10 +1,15 @@
 use super::error::Error;
-use super::structs::{Command, Dimension, Indexed, SearchResult, State};
+use super::history::{History, DEFAULT_MAX_ENTRIES};
+use super::structs::{
+    dependency_line, fetch_size, Command, CopyToClipboard, Destination, Details, Dimension,
+    Indexed, Movement, SearchResult, State,
+};
 use futures::{self, sync::mpsc, Future, Sink, Stream};
 use open;
 use std::{
     cell::RefCell,
-    cmp::max,
+    collections::HashMap,
+    env,
     fmt::Display,
     io::{self, Write},
     rc::Rc,
@@ -16,15 +21,23 @@ use std::{
 use termion::{clear, cursor, event::Key, input::TermRead, raw::IntoRawMode};
 use tokio_core::reactor::{Core, Handle, Timeout};
 use tokio_curl::Session;
-use urlencoding;
 
-use crate::http_utils::{
-    paged_crates_io_remote_call, CallMetaData, CallResult, DropOutdated, DroppedOrError,
+use super::cmd::{handle_piped_search, search_url};
+use crate::{
+    args::SearchSort,
+    http_utils::{
+        paged_crates_io_remote_call, paged_crates_io_remote_call_from, remote_call, CallMetaData,
+        CallResult, DropOutdated, DroppedOrError,
+    },
+    manifest::{self, Section},
+    output::Options,
+    structs::{Crate, CrateResponse, OwnersResponse},
 };
 
 const INFO_LINE: cursor::Goto = cursor::Goto(1, 2);
 const CONTENT_LINE: cursor::Goto = cursor::Goto(1, 3);
 const NON_CONTENT_LINES: u16 = 2;
+const DETAIL_LINES: u16 = 6;
 
 fn search_result_from_callresult(c: CallResult) -> Result<SearchResult, Error> {
     let (buf, _) = c;
@@ -40,14 +53,14 @@ fn search_result_from_callresult(c: CallResult) -> Result<SearchResult, Error> {
     })
 }
 
-fn merge(mut r: SearchResult, c: CallResult) -> Result<SearchResult, Error> {
+pub(super) fn merge(mut r: SearchResult, c: CallResult) -> Result<SearchResult, Error> {
     search_result_from_callresult(c).map(|mut res| {
         r.crates.append(&mut res.crates);
         r
     })
 }
 
-fn extract(c: CallResult) -> Result<(CallMetaData, SearchResult), Error> {
+pub(super) fn extract(c: CallResult) -> Result<(CallMetaData, SearchResult), Error> {
     search_result_from_callresult(c).map(|res| {
         (
             CallMetaData {
@@ -73,7 +86,7 @@ where
 }
 
 fn dimension() -> Dimension {
-    Dimension::default().loose_heigth(NON_CONTENT_LINES)
+    Dimension::default().loose_heigth(NON_CONTENT_LINES + DETAIL_LINES)
 }
 
 use super::structs::Command::*;
@@ -85,43 +98,81 @@ enum ReducerDo {
     ShowLast,
     Show(SearchResult),
     DrawIndices,
-    Open { force: bool, number: usize },
+    Open {
+        force: bool,
+        number: usize,
+        destination: Destination,
+    },
+    Select(Movement),
+    /// The crates of `page` follow the first `first` crates of the current result, which make room for `movement`
+    More {
+        first: usize,
+        page: SearchResult,
+        movement: Movement,
+    },
+    OpenSelected(Destination),
+    CopySelected {
+        with_features: bool,
+    },
+    AddSelected(Section),
+    Details(Box<Details>),
+}
+
+/// What the worker thread keeps between commands
+struct Worker {
+    api_url: String,
+    handle: Handle,
+    session: Arc<Mutex<Session>>,
+    version: Arc<AtomicUsize>,
+    details_version: Arc<AtomicUsize>,
+    current_result: RefCell<Option<SearchResult>>,
+    details: RefCell<HashMap<String, Details>>,
 }
 
 fn setup_future(
     cmd: Command,
+    api_url: &str,
     session: Arc<Mutex<Session>>,
     handle: &Handle,
     version: &Arc<AtomicUsize>,
 ) -> Box<dyn Future<Item = ReducerDo, Error = Error> + Send> {
     match cmd {
         Clear => Box::new(futures::finished(ReducerDo::Clear)),
-        Open { force, number } => Box::new(futures::finished(ReducerDo::Open {
-            force: force,
-            number: number,
+        Open {
+            force,
+            number,
+            destination,
+        } => Box::new(futures::finished(ReducerDo::Open {
+            force,
+            number,
+            destination,
         })),
         DrawIndices => Box::new(futures::finished(ReducerDo::DrawIndices)),
         ShowLast => Box::new(futures::finished(ReducerDo::ShowLast)),
-        Search(term) => {
+        Select(movement) => Box::new(futures::finished(ReducerDo::Select(movement))),
+        OpenSelected(destination) => {
+            Box::new(futures::finished(ReducerDo::OpenSelected(destination)))
+        }
+        CopySelected { with_features } => {
+            Box::new(futures::finished(ReducerDo::CopySelected { with_features }))
+        }
+        AddSelected(section) => Box::new(futures::finished(ReducerDo::AddSelected(section))),
+        Search(term, sort) => {
             let version = {
                 version.fetch_add(1, Ordering::SeqCst);
                 version.clone()
             };
 
             let dim = dimension();
-            let url = format!(
-                "https://crates.io/api/v1/crates?page=1&per_page={}&q={}&sort=",
-                max(100, dim.height),
-                urlencoding::encode(&term)
-            );
+            let url = search_url(api_url, &term, sort);
             let req = paged_crates_io_remote_call(
                 &url,
-                Some(dim.height as u32),
+                Some(fetch_size(dim.height as usize).1),
                 session.clone(),
                 merge,
                 extract,
             );
-            info(&"searching ...");
+            info(&format!("searching by {} ...", sort));
             let default_timeout: Duration = Duration::from_millis(15000);
             let timeout = Timeout::new(default_timeout.clone(), handle)
                 .map(|f| Box::new(f) as Box<dyn Future<Item = _, Error = _> + Send>)
@@ -142,6 +193,7 @@ fn setup_future(
                 })
                 .map(move |mut result| {
                     result.meta.term = Some(term);
+                    result.meta.sort = sort;
                     ReducerDo::Show(result)
                 });
 
@@ -166,9 +218,132 @@ fn setup_future(
     }
 }
 
+/// Fetch the page of crates following the ones of `search`, to do `movement` once it arrived.
+fn setup_page_future(
+    search: &SearchResult,
+    movement: Movement,
+    api_url: &str,
+    session: Arc<Mutex<Session>>,
+    version: &Arc<AtomicUsize>,
+) -> Box<dyn Future<Item = ReducerDo, Error = Error> + Send> {
+    let first = search.crates.len();
+    let (first_page, max_items) = search.next_fetch();
+    let url = search_url(
+        api_url,
+        search.meta.term.as_deref().unwrap_or_default(),
+        search.meta.sort,
+    );
+    info(&"loading more results ...");
+    let req = paged_crates_io_remote_call_from(
+        &url,
+        first_page,
+        Some(max_items),
+        session,
+        merge,
+        extract,
+    )
+    .map_err(move |e| {
+        info(&format!("Request to {} failed with error: '{}'", url, e));
+        e.into()
+    })
+    .map(move |page| ReducerDo::More {
+        first,
+        page,
+        movement,
+    });
+    Box::new(
+        DropOutdated::with_version(req, version.clone()).or_else(|e| match e {
+            DroppedOrError::Dropped => Ok(ReducerDo::Nothing),
+            DroppedOrError::Err(e) => Err(e),
+        }),
+    )
+}
+
+fn setup_details_future(
+    name: String,
+    api_url: &str,
+    session: Arc<Mutex<Session>>,
+    version: &Arc<AtomicUsize>,
+) -> Box<dyn Future<Item = ReducerDo, Error = Error> + Send> {
+    let version = {
+        version.fetch_add(1, Ordering::SeqCst);
+        version.clone()
+    };
+    let url = format!("{}/api/v1/crates/{}", api_url, urlencoding::encode(&name));
+    let req = remote_call(&url, session.clone())
+        .join(remote_call(&format!("{}/owners", url), session))
+        .map_err(Error::from)
+        .and_then(|((krate, _), (owners, _))| {
+            let CrateResponse { krate, versions } = serde_json::from_slice(&krate.lock().unwrap())?;
+            let OwnersResponse { users } = serde_json::from_slice(&owners.lock().unwrap())?;
+            Ok(ReducerDo::Details(Box::new(Details {
+                krate,
+                versions,
+                owners: users,
+            })))
+        })
+        .map_err(move |e| {
+            info(&format!(
+                "Details of '{}' could not be fetched: '{}'",
+                name, e
+            ));
+            e
+        });
+    Box::new(
+        DropOutdated::with_version(req, version).or_else(|e| match e {
+            DroppedOrError::Dropped => Ok(ReducerDo::Nothing),
+            DroppedOrError::Err(e) => Err(e),
+        }),
+    )
+}
+
+/// Draw the details of the selected crate below the search results, or nothing if there is none.
+fn draw_details(search: Option<&SearchResult>, details: &HashMap<String, Details>) {
+    let dim = dimension();
+    let lines = match search.and_then(SearchResult::selected_crate) {
+        Some(krate) => match details.get(&krate.name) {
+            Some(details) => details.lines(),
+            None => vec![format!("Loading details of '{}' ...", krate.name)],
+        },
+        None => Vec::new(),
+    };
+    let first_line = CONTENT_LINE.1 + dim.height;
+    for (line, text) in (first_line..first_line + DETAIL_LINES).zip(
+        lines
+            .iter()
+            .map(String::as_str)
+            .chain(std::iter::repeat("")),
+    ) {
+        write!(
+            io::stdout(),
+            "{goto}{clear}{:.max$}",
+            text,
+            goto = cursor::Goto(1, line),
+            clear = clear::CurrentLine,
+            max = dim.width as usize
+        )
+        .ok();
+    }
+}
+
+fn open_crate(api_url: &str, krate: &Crate, destination: Destination) {
+    match destination.url(api_url, krate) {
+        Some(url) => {
+            if let Err(e) = open::that(url) {
+                info(&e);
+            }
+        }
+        None => {
+            info(&format!("'{}' has no {}", krate.name, destination));
+        }
+    }
+}
+
 fn handle_future_result(
     cmd: ReducerDo,
-    current_result: Option<&SearchResult>,
+    api_url: &str,
+    current_result: Option<&mut SearchResult>,
+    details: &mut HashMap<String, Details>,
 ) -> Option<Option<SearchResult>> {
     use self::ReducerDo::*;
     let mut res = None;
@@ -179,8 +354,8 @@ fn handle_future_result(
         }
         (DrawIndices, Some(ref search)) => {
             info(
-                &"(<ESC> to quit, Ctrl+o to cancel, <enter> to confirm) Type the number of the \
-                  crate to open.",
+                &"(<ESC> to quit, Ctrl+o to cancel, <enter> to confirm, Ctrl+d/g/w for docs, \
+                  repository or homepage) Type the number of the crate to open.",
             );
             write!(
                 io::stdout(),
@@ -193,17 +368,17 @@ fn handle_future_result(
         (Open { .. }, None) => {
             info(&"There is nothing to open - conduct a search first");
         }
-        (Open { force, number }, Some(search)) => match search.crates.get(number) {
+        (
+            Open {
+                force,
+                number,
+                destination,
+            },
+            Some(search),
+        ) => match search.crates.get(number) {
             Some(c1) => {
                 if number == 0 || search.crates.get(number * 10).is_none() || force {
-                    let url = format!(
-                        "https://crates.io/crates/{n}/{v}",
-                        n = c1.name,
-                        v = c1.max_version
-                    );
-                    if let Err(e) = open::that(url) {
-                        info(&e);
-                    }
+                    open_crate(api_url, c1, destination);
                 } else {
                     info(&format!(
                         "Hit <enter> to open crate #{} or keep typing ...",
@@ -215,29 +390,99 @@ fn handle_future_result(
                 info(&format!("No crate #{}! Try using <backspace> ...", number));
             }
         },
+        (Select(_), None)
+        | (OpenSelected(_), None)
+        | (CopySelected { .. }, None)
+        | (AddSelected(_), None) => {
+            info(&"There is nothing to select - conduct a search first.");
+        }
+        (More { .. }, None) => {}
+        (
+            More {
+                first,
+                page,
+                movement,
+            },
+            Some(search),
+        ) => {
+            if search.crates.len() == first {
+                search.crates.extend(page.crates);
+            }
+            show_selection(search, movement, details);
+        }
+        (Select(movement), Some(search)) => show_selection(search, movement, details),
+        (Details(crate_details), search) => {
+            details.insert(crate_details.krate.name.clone(), *crate_details);
+            draw_details(search.map(|s| &*s), details);
+        }
+        (OpenSelected(destination), Some(search)) => {
+            match search.meta.selected.and_then(|s| search.crates.get(s)) {
+                Some(krate) => open_crate(api_url, krate, destination),
+                None => {
+                    info(&"Nothing is selected - use <up> or <down> to select a crate.");
+                }
+            }
+        }
+        (CopySelected { with_features }, Some(search)) => match search.selected_crate() {
+            Some(krate) => {
+                let line = dependency_line(krate, with_features);
+                write!(io::stdout(), "{}", CopyToClipboard(&line)).ok();
+                info(&format!("Copied '{}' to the clipboard", line));
+            }
+            None => {
+                info(&"Nothing is selected - use <up> or <down> to select a crate.");
+            }
+        },
+        (AddSelected(section), Some(search)) => match search.selected_crate() {
+            Some(krate) => {
+                let added = env::current_dir()
+                    .map_err(|err| err.to_string())
+                    .and_then(|dir| manifest::find(&dir).map_err(|err| err.to_string()))
+                    .and_then(|path| {
+                        manifest::add_dependency_to_file(
+                            &path,
+                            section,
+                            &krate.name,
+                            &krate.max_version,
+                        )
+                        .map(|_| path)
+                        .map_err(|err| err.to_string())
+                    });
+                info(&match added {
+                    Ok(path) => format!(
+                        "Added {} = \"{}\" to [{}] of '{}'",
+                        krate.name,
+                        krate.max_version,
+                        section,
+                        path.display()
+                    ),
+                    Err(err) => err,
+                });
+            }
+            None => {
+                info(&"Nothing is selected - use <up> or <down> to select a crate.");
+            }
+        },
         (Clear, _) => {
             usage();
             let empty_search = SearchResult::with_dimension(dimension());
             write!(io::stdout(), "{goto}{}", empty_search, goto = CONTENT_LINE).ok();
+            draw_details(None, details);
             res = Some(None);
         }
         (ShowLast, None) => {
             info(&"There is no previous result - conduct a search first.");
         }
-        (ShowLast, Some(ref search)) => {
+        (ShowLast, Some(search)) => {
             write!(io::stdout(), "{goto}{}", search, goto = CONTENT_LINE).ok();
+            draw_details(Some(search), details);
         }
         (Show(result), last_search) => {
             info(&format!(
-                "{} results for '{}' in total, showing {} max",
-                result.meta.total,
+                "Results for '{}' by {}, {}",
                 result.meta.term.as_ref().map(|s| s.as_str()).unwrap_or(""),
-                result
-                    .meta
-                    .dimension
-                    .as_ref()
-                    .expect("dimension to be set")
-                    .height
+                result.meta.sort,
+                result.rows()
             ));
             if result.crates.is_empty() {
                 let last = usage();
@@ -254,6 +499,7 @@ fn handle_future_result(
                 .ok();
             } else {
                 write!(io::stdout(), "{goto}{}", result, goto = CONTENT_LINE).ok();
+                draw_details(Some(&result), details);
                 res = Some(Some(result));
             }
         }
@@ -262,6 +508,26 @@ fn handle_future_result(
     res
 }
 
+/// Move the highlighted row of `search` and show the page it is on.
+fn show_selection(
+    search: &mut SearchResult,
+    movement: Movement,
+    details: &HashMap<String, Details>,
+) {
+    if let Some(krate) = search.select(movement) {
+        let name = krate.name.clone();
+        info(&format!(
+            "{} (<up>/<down>/<page-up>/<page-down> to move, <enter> to open, Ctrl+d/g/w for \
+             docs, repository or homepage, Ctrl+y/Alt+y to copy as dependency, Ctrl+a/Alt+a to add \
+             to the (dev-)dependencies of Cargo.toml) {}",
+            search.rows(),
+            name
+        ));
+    }
+    write!(io::stdout(), "{goto}{}", search, goto = CONTENT_LINE).ok();
+    draw_details(Some(search), details);
+}
+
 enum LoopControl {
     ShouldBreak,
     ShouldKeepGoing,
@@ -274,8 +540,107 @@ fn handle_key(
 ) -> Result<LoopControl, Error> {
     let (mut force_open, mut show_last_search) = (false, false);
     match k {
+        Key::Char('\t') | Key::BackTab if matches!(state.mode, Searching) => {
+            state.selecting = false;
+            state.sort = match k {
+                Key::BackTab => state.sort.previous(),
+                _ => state.sort.next(),
+            };
+            if state.term.is_empty() {
+                info(&format!("Results will be sorted by {}", state.sort));
+                return Ok(LoopControl::ShouldKeepGoing);
+            }
+        }
+        Key::Up | Key::Down | Key::PageUp | Key::PageDown if matches!(state.mode, Searching) => {
+            state.selecting = true;
+            let movement = match k {
+                Key::Up => Movement::Up,
+                Key::Down => Movement::Down,
+                Key::PageUp => Movement::PageUp,
+                _ => Movement::PageDown,
+            };
+            sender
+                .send(Select(movement))
+                .wait()
+                .map_err(Error::SendCommand)?;
+            return Ok(LoopControl::ShouldKeepGoing);
+        }
+        Key::Char('\n') if state.selecting && matches!(state.mode, Searching) => {
+            remember(state);
+            sender
+                .send(OpenSelected(Destination::CratesIo))
+                .wait()
+                .map_err(Error::SendCommand)?;
+            return Ok(LoopControl::ShouldKeepGoing);
+        }
+        Key::Ctrl('y') | Key::Alt('y') if matches!(state.mode, Searching) => {
+            sender
+                .send(CopySelected {
+                    with_features: k == Key::Alt('y'),
+                })
+                .wait()
+                .map_err(Error::SendCommand)?;
+            return Ok(LoopControl::ShouldKeepGoing);
+        }
+        Key::Ctrl('a') | Key::Alt('a') if matches!(state.mode, Searching) => {
+            sender
+                .send(AddSelected(if k == Key::Alt('a') {
+                    Section::Dev
+                } else {
+                    Section::Normal
+                }))
+                .wait()
+                .map_err(Error::SendCommand)?;
+            return Ok(LoopControl::ShouldKeepGoing);
+        }
+        Key::Ctrl(c @ 'd') | Key::Ctrl(c @ 'g') | Key::Ctrl(c @ 'w') => {
+            let destination = match c {
+                'd' => Destination::Docs,
+                'g' => Destination::Repository,
+                _ => Destination::Homepage,
+            };
+            let cmd = match state.mode {
+                Searching => OpenSelected(destination),
+                Opening => match state.number.parse() {
+                    Ok(number) => Open {
+                        force: true,
+                        number,
+                        destination,
+                    },
+                    Err(_) => {
+                        info(&"Type the number of the crate to open first.");
+                        return Ok(LoopControl::ShouldKeepGoing);
+                    }
+                },
+            };
+            sender.send(cmd).wait().map_err(Error::SendCommand)?;
+            return Ok(LoopControl::ShouldKeepGoing);
+        }
+        Key::Ctrl(c @ 'p') | Key::Ctrl(c @ 'n') | Key::Ctrl(c @ 'r')
+            if matches!(state.mode, Searching) =>
+        {
+            let recalled = match c {
+                'p' => state.history.previous(),
+                'n' => state.history.next(),
+                _ => state.history.search_backward(&state.term),
+            }
+            .map(ToOwned::to_owned);
+            match recalled {
+                Some(term) => {
+                    state.selecting = false;
+                    state.term = term;
+                }
+                None => {
+                    info(&"There is no such search in the history.");
+                    return Ok(LoopControl::ShouldKeepGoing);
+                }
+            }
+        }
         Key::Char('\n') => match state.mode {
-            Searching => state.term.clear(),
+            Searching => {
+                remember(state);
+                state.term.clear()
+            }
             Opening => {
                 force_open = true;
                 state.mode = Opening;
@@ -283,9 +648,9 @@ fn handle_key(
         },
         Key::Char(c) => match state.mode {
             Searching => {
-                if !is_special(c) {
-                    state.term.push(c)
-                }
+                state.selecting = false;
+                state.history.reset();
+                state.term.push(c)
             }
             Opening => match c {
                 '0' | '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => state.number.push(c),
@@ -297,7 +662,11 @@ fn handle_key(
         },
         Key::Backspace => {
             match state.mode {
-                Searching => &mut state.term,
+                Searching => {
+                    state.selecting = false;
+                    state.history.reset();
+                    &mut state.term
+                }
                 Opening => &mut state.number,
             }
             .pop();
@@ -313,6 +682,9 @@ fn handle_key(
             };
         }
         Key::Esc | Key::Ctrl('c') => {
+            if let Searching = state.mode {
+                remember(state);
+            }
             return Ok(LoopControl::ShouldBreak);
         }
         key @ _ => {
@@ -328,12 +700,13 @@ fn handle_key(
             } else {
                 match show_last_search {
                     true => ShowLast,
-                    false => Search(state.term.clone()),
+                    false => Search(state.term.clone(), state.sort),
                 }
             }
         }
         Opening if state.number.len() > 0 => Open {
             force: force_open,
+            destination: Destination::CratesIo,
             number: match state.number.parse() {
                 Ok(n) => n,
                 Err(e) => {
@@ -349,45 +722,59 @@ fn handle_key(
     return Ok(LoopControl::ShouldKeepGoing);
 }
 
-pub fn handle_interactive_search() -> Result<(), Error> {
+/// Add the current search term to the history.
+fn remember(state: &mut State) {
+    if let Err(e) = state.history.add(&state.term) {
+        info(&format!("The search history could not be saved: {}", e));
+    }
+}
+
+/// Search interactively starting with the `sort` order, remembering `history_size` search terms, or a default
+/// amount if unset. Without a terminal, the `limit` results for a piped search term are printed with `options`.
+pub fn handle_interactive_search(
+    api_url: String,
+    history_size: Option<usize>,
+    sort: SearchSort,
+    limit: u32,
+    options: Options,
+) -> Result<(), Error> {
+    if !termion::is_tty(&io::stdout()) {
+        return handle_piped_search(sort, limit, &api_url, options);
+    }
     let stdin = io::stdin();
     let mut stdout = io::stdout().into_raw_mode()?;
-    let mut state = State::default();
+    let mut state = State {
+        history: History::load(history_size.unwrap_or(DEFAULT_MAX_ENTRIES)),
+        sort,
+        ..Default::default()
+    };
 
     write!(stdout, "{}{}", cursor::Goto(1, 1), clear::All).map_err(Error::FirstIo)?;
     promptf(&state);
     usage();
 
     let (sender, receiver) = mpsc::channel(10);
-    let t = thread::spawn(|| {
+    let t = thread::spawn(move || {
         let mut reactor = match Core::new() {
             Err(e) => return Err(Error::ReactorInit(e)),
             Ok(r) => r,
         };
-        let session = Arc::new(Mutex::new(Session::new(reactor.handle())));
-        let handle = reactor.handle();
-        let version = Arc::new(AtomicUsize::new(0));
-        let current_result = Rc::new(RefCell::new(None));
+        let worker = Rc::new(Worker {
+            api_url,
+            handle: reactor.handle(),
+            session: Arc::new(Mutex::new(Session::new(reactor.handle()))),
+            version: Arc::new(AtomicUsize::new(0)),
+            details_version: Arc::new(AtomicUsize::new(0)),
+            current_result: RefCell::new(None),
+            details: RefCell::new(HashMap::new()),
+        });
 
         let commands = receiver
             .and_then(|cmd: Command| {
-                let cr = current_result.clone();
-                let spawnable = setup_future(cmd, session.clone(), &handle, &version)
-                    .then(|r| {
-                        match r {
-                            Ok(r) => Ok(r),
-                            Err(Error::DecodeJson(_)) => Err(()), /*abort stream on decode error*/
-                            Err(_) => Ok(ReducerDo::Nothing),     /*ignore other errors*/
-                        }
-                    })
-                    .and_then(move |result| {
-                        let res = handle_future_result(result, cr.borrow().as_ref());
-                        if let Some(next_result) = res {
-                            *cr.borrow_mut() = next_result;
-                        }
-                        Ok(())
-                    });
-                handle.spawn(spawnable);
+                let w = &worker;
+                let future =
+                    setup_future(cmd, &w.api_url, w.session.clone(), &w.handle, &w.version);
+                reduce(worker.clone(), future);
                 Ok(())
             })
             .for_each(|_| Ok(()));
@@ -408,6 +795,71 @@ pub fn handle_interactive_search() -> Result<(), Error> {
     res
 }
 
+/// Run `future` and handle its result, which may involve running more futures.
+fn reduce(worker: Rc<Worker>, future: Box<dyn Future<Item = ReducerDo, Error = Error> + Send>) {
+    let handle = worker.handle.clone();
+    let spawnable = future
+        .then(|r| {
+            match r {
+                Ok(r) => Ok(r),
+                Err(Error::DecodeJson(_)) => Err(()), /*abort stream on decode error*/
+                Err(_) => Ok(ReducerDo::Nothing),     /*ignore other errors*/
+            }
+        })
+        .and_then(move |result| {
+            if let ReducerDo::Select(movement) = result {
+                let page_future = worker
+                    .current_result
+                    .borrow()
+                    .as_ref()
+                    .filter(|search| search.needs_more(movement))
+                    .map(|search| {
+                        setup_page_future(
+                            search,
+                            movement,
+                            &worker.api_url,
+                            worker.session.clone(),
+                            &worker.version,
+                        )
+                    });
+                if let Some(future) = page_future {
+                    reduce(worker, future);
+                    return Ok(());
+                }
+            }
+            let selection_changed = matches!(result, ReducerDo::Select(_) | ReducerDo::More { .. });
+            let res = handle_future_result(
+                result,
+                &worker.api_url,
+                worker.current_result.borrow_mut().as_mut(),
+                &mut worker.details.borrow_mut(),
+            );
+            if let Some(next_result) = res {
+                *worker.current_result.borrow_mut() = next_result;
+            }
+            if selection_changed {
+                let missing_details = worker
+                    .current_result
+                    .borrow()
+                    .as_ref()
+                    .and_then(SearchResult::selected_crate)
+                    .map(|krate| krate.name.clone())
+                    .filter(|name| !worker.details.borrow().contains_key(name));
+                if let Some(name) = missing_details {
+                    let future = setup_details_future(
+                        name,
+                        &worker.api_url,
+                        worker.session.clone(),
+                        &worker.details_version,
+                    );
+                    reduce(worker.clone(), future);
+                }
+            }
+            Ok(())
+        });
+    handle.spawn(spawnable);
+}
+
 fn reset_terminal() {
     write!(
         io::stdout(),
@@ -420,7 +872,10 @@ fn reset_terminal() {
 }
 
 fn usage() -> usize {
-    info(&"(<ESC> to quit, <enter> to clear, Ctrl+o to open) Please enter your search term.")
+    info(
+        &"(<ESC> to quit, <enter> to clear, <up>/<down> to select, Ctrl+o to open by number, <tab> \
+          to change the order, Ctrl+p/n/r to recall) Please enter your search term.",
+    )
 }
 
 fn info(item: &dyn Display) -> usize {
@@ -451,7 +906,3 @@ fn promptf(state: &State) {
     .ok();
     io::stdout().flush().ok();
 }
-
-fn is_special(c: char) -> bool {
-    c == '\t'
-}
@@ -4,6 +4,10 @@
 //! would rather solve new problems than changing existing solutions, despite them
 //! being pretty complex for what they do.
 #[cfg(unix)]
+mod cmd;
+#[cfg(unix)]
+mod history;
+#[cfg(unix)]
 mod interactive;
 #[cfg(unix)]
 mod structs;
@@ -11,11 +15,34 @@ mod structs;
 mod error;
 pub use self::error::Error;
 
+/// The amount of crates to print if no limit is given
+pub const DEFAULT_LIMIT: u32 = 100;
+
+#[cfg(unix)]
+pub use self::cmd::handle_search;
 #[cfg(unix)]
 pub use self::interactive::handle_interactive_search;
 
 #[cfg(windows)]
-pub fn handle_interactive_search() -> Result<(), Error> {
+pub fn handle_interactive_search(
+    _api_url: String,
+    _history_size: Option<usize>,
+    _sort: crate::args::SearchSort,
+    _limit: u32,
+    _options: crate::output::Options,
+) -> Result<(), Error> {
     println!("Interactive search is not supported. Use --help to learn about alternatives.");
     std::process::exit(3);
 }
+
+#[cfg(windows)]
+pub fn handle_search(
+    _query: String,
+    _sort: crate::args::SearchSort,
+    _limit: u32,
+    _api_url: &str,
+    _options: crate::output::Options,
+) -> Result<(), Error> {
+    println!("Search is not supported. Use --help to learn about alternatives.");
+    std::process::exit(3);
+}
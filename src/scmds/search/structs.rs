@@ -1,4 +1,10 @@
-use crate::structs::Crate;
+use super::history::History;
+use crate::{
+    args::SearchSort,
+    http_utils::MAX_ITEMS_PER_PAGE,
+    manifest::Section,
+    structs::{Crate, Owner, Version},
+};
 use serde_derive::Deserialize;
 
 use std::{
@@ -8,7 +14,7 @@ use std::{
     iter, str,
 };
 
-use termion::{clear, cursor};
+use termion::{clear, cursor, style};
 
 const CRATE_ROW_OVERHEAD: u16 = 3 * 3;
 
@@ -20,7 +26,7 @@ pub struct Dimension {
 
 impl Dimension {
     pub fn loose_heigth(mut self, h: u16) -> Dimension {
-        self.height -= h;
+        self.height = self.height.saturating_sub(h);
         self
     }
 }
@@ -60,6 +66,14 @@ fn sanitize(input: &str) -> String {
 pub struct Meta {
     pub total: u32,
     pub term: Option<String>,
+    #[serde(skip)]
+    pub sort: SearchSort,
+    /// The index of the highlighted crate, if any
+    #[serde(skip)]
+    pub selected: Option<usize>,
+    /// The index of the first crate shown, always at the start of a page
+    #[serde(skip)]
+    pub offset: usize,
     pub dimension: Option<Dimension>,
 }
 
@@ -142,13 +156,106 @@ fn desired_string_widths(items: &[Crate], max_width: u16) -> (usize, usize, usiz
     (w[3], w[0], w[1], w[2])
 }
 
+/// The size of the pages to fetch crates in to fill `rows` rows, which the server limits, and the
+/// amount of crates to fetch for that, which is a whole number of pages.
+pub fn fetch_size(rows: usize) -> (u32, u32) {
+    let rows = rows as u32;
+    let per_page = rows.clamp(1, MAX_ITEMS_PER_PAGE);
+    (per_page, rows.div_ceil(per_page) * per_page)
+}
+
 #[derive(Deserialize, Default)]
 pub struct SearchResult {
     pub crates: Vec<Crate>,
     pub meta: Meta,
 }
 
+/// A way to move the highlighted row
+#[derive(Clone, Copy)]
+pub enum Movement {
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+}
+
 impl SearchResult {
+    /// The amount of crates which can be shown at once, which is also the size of the pages to fetch
+    pub fn page_size(&self) -> usize {
+        self.meta
+            .dimension
+            .as_ref()
+            .map_or(0, |d| d.height as usize)
+    }
+
+    /// The crates which are currently shown
+    fn page(&self) -> &[Crate] {
+        let start = cmp::min(self.meta.offset, self.crates.len());
+        let end = cmp::min(start + self.page_size(), self.crates.len());
+        &self.crates[start..end]
+    }
+
+    /// The index of the crate `movement` leads to, which might not have been fetched yet.
+    /// Without a highlighted row, the first one shown is selected.
+    fn target(&self, movement: Movement) -> Option<usize> {
+        let page = match self.page_size() {
+            0 => return None,
+            page => page,
+        };
+        Some(match (self.meta.selected, movement) {
+            (None, Movement::Up) | (None, Movement::Down) => self.meta.offset,
+            (selected, movement) => {
+                let s = selected.unwrap_or(self.meta.offset);
+                match movement {
+                    Movement::Up => s.saturating_sub(1),
+                    Movement::Down => s + 1,
+                    Movement::PageUp => s.saturating_sub(page),
+                    Movement::PageDown => s + page,
+                }
+            }
+        })
+    }
+
+    /// The page to continue fetching crates at, and how many of them to fetch to fill another screen.
+    pub fn next_fetch(&self) -> (u32, u32) {
+        let (per_page, max_items) = fetch_size(self.page_size());
+        (self.crates.len() as u32 / per_page + 1, max_items)
+    }
+
+    /// True if crates shown after `movement` have to be fetched first.
+    pub fn needs_more(&self, movement: Movement) -> bool {
+        let page = self.page_size();
+        self.target(movement).is_some_and(|target| {
+            let page_end = (target / page + 1) * page;
+            cmp::min(page_end, self.meta.total as usize) > self.crates.len()
+        })
+    }
+
+    /// Move the highlighted row, showing the page it is on.
+    /// Returns the crate which is selected afterwards.
+    pub fn select(&mut self, movement: Movement) -> Option<&Crate> {
+        let last = self.crates.len().checked_sub(1)?;
+        let selected = cmp::min(self.target(movement)?, last);
+        self.meta.selected = Some(selected);
+        self.meta.offset = selected - selected % self.page_size();
+        self.selected_crate()
+    }
+
+    /// Which of all the crates found are shown, like 'rows 17–32 of 250'.
+    pub fn rows(&self) -> String {
+        let page = self.page();
+        format!(
+            "rows {}–{} of {}",
+            cmp::min(self.meta.offset + 1, self.meta.offset + page.len()),
+            self.meta.offset + page.len(),
+            self.meta.total
+        )
+    }
+
+    pub fn selected_crate(&self) -> Option<&Crate> {
+        self.meta.selected.and_then(|s| self.crates.get(s))
+    }
+
     pub fn with_dimension(dim: Dimension) -> SearchResult {
         SearchResult {
             meta: Meta {
@@ -169,19 +276,27 @@ impl SearchResult {
 impl Display for SearchResult {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let dim = self.meta.dimension.as_ref().expect("dimension to be set");
-        let max_width = desired_table_widths(&self.crates, &dim);
-        for krate in self
-            .crates
+        let max_width = desired_table_widths(self.page(), &dim);
+        for (index, krate) in self
+            .page()
             .iter()
             .cloned()
             .chain(iter::repeat(Crate::default()))
             .take(dim.height as usize)
+            .enumerate()
         {
             let krate = format!("{}", CrateRow(&krate, &max_width));
+            let (highlight, reset) = if self.meta.selected == Some(self.meta.offset + index) {
+                (format!("{}", style::Invert), format!("{}", style::Reset))
+            } else {
+                (String::new(), String::new())
+            };
             write!(
                 f,
-                "{clear}{:.max$}{down}{left}",
+                "{clear}{highlight}{:.max$}{reset}{down}{left}",
                 krate,
+                highlight = highlight,
+                reset = reset,
                 clear = clear::CurrentLine,
                 down = cursor::Down(1),
                 left = cursor::Left(cmp::max(krate.len(), dim.width as usize) as u16),
@@ -192,11 +307,164 @@ impl Display for SearchResult {
     }
 }
 
+/// Everything to show about a crate in the detail pane
+pub struct Details {
+    pub krate: Crate,
+    pub versions: Vec<Version>,
+    pub owners: Vec<Owner>,
+}
+
+const SHOWN_VERSIONS: usize = 5;
+
+impl Details {
+    /// The lines of the detail pane, without limiting their width
+    pub fn lines(&self) -> Vec<String> {
+        let krate = &self.krate;
+        let license = krate
+            .license
+            .clone()
+            .or_else(|| self.versions.first().and_then(|v| v.license.clone()))
+            .unwrap_or_else(|| "unknown license".into());
+        let versions: Vec<_> = self
+            .versions
+            .iter()
+            .filter(|v| !v.yanked)
+            .take(SHOWN_VERSIONS)
+            .map(|v| v.num.as_str())
+            .collect();
+        let owners: Vec<_> = self.owners.iter().map(|o| o.login.as_str()).collect();
+        vec![
+            format!(
+                "{bold}{}{reset} {} ({})",
+                krate.name,
+                krate.max_version,
+                license,
+                bold = style::Bold,
+                reset = style::Reset
+            ),
+            krate
+                .description
+                .as_ref()
+                .map(|d| sanitize(d.trim()))
+                .unwrap_or_else(|| "no description".into()),
+            format!(
+                "repository:    {}",
+                krate.repository.as_deref().unwrap_or("-")
+            ),
+            format!(
+                "documentation: {}",
+                krate
+                    .documentation
+                    .clone()
+                    .unwrap_or_else(|| format!("https://docs.rs/{}", krate.name))
+            ),
+            format!("versions:      {}", versions.join(", ")),
+            format!("owners:        {}", owners.join(", ")),
+        ]
+    }
+}
+
+/// The page of a crate to open in the browser
+#[derive(Clone, Copy)]
+pub enum Destination {
+    CratesIo,
+    Docs,
+    Repository,
+    Homepage,
+}
+
+impl Destination {
+    /// The url of the page, if the crate has one.
+    pub fn url(self, api_url: &str, krate: &Crate) -> Option<String> {
+        match self {
+            Destination::CratesIo => Some(format!(
+                "{}/crates/{}/{}",
+                api_url, krate.name, krate.max_version
+            )),
+            Destination::Docs => Some(format!(
+                "https://docs.rs/{}/{}",
+                krate.name, krate.max_version
+            )),
+            Destination::Repository => krate.repository.clone(),
+            Destination::Homepage => krate.homepage.clone(),
+        }
+    }
+}
+
+impl Display for Destination {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Destination::CratesIo => "page on crates.io",
+            Destination::Docs => "documentation",
+            Destination::Repository => "repository",
+            Destination::Homepage => "homepage",
+        })
+    }
+}
+
+/// The line to add to the `[dependencies]` of a manifest to depend on the latest stable version of `krate`,
+/// optionally with room for features.
+pub fn dependency_line(krate: &Crate, with_features: bool) -> String {
+    let version = krate
+        .max_stable_version
+        .as_ref()
+        .unwrap_or(&krate.max_version);
+    if with_features {
+        format!(
+            "{} = {{ version = \"{}\", features = [] }}",
+            krate.name, version
+        )
+    } else {
+        format!("{} = \"{}\"", krate.name, version)
+    }
+}
+
+fn base64(input: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (bytes[0] as usize) << 16 | (bytes[1] as usize) << 8 | bytes[2] as usize;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[n >> (18 - 6 * i) & 0x3f] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// The escape sequence which makes the terminal put `text` into the system clipboard (OSC 52).
+/// It also works over SSH, as long as the terminal supports it.
+pub struct CopyToClipboard<'a>(pub &'a str);
+
+impl<'a> Display for CopyToClipboard<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\x1b]52;c;{}\x07", base64(self.0.as_bytes()))
+    }
+}
+
 #[derive(Clone)]
 pub enum Command {
-    Search(String),
+    Search(String, SearchSort),
+    Select(Movement),
+    OpenSelected(Destination),
+    CopySelected {
+        with_features: bool,
+    },
+    AddSelected(Section),
     ShowLast,
-    Open { force: bool, number: usize },
+    Open {
+        force: bool,
+        number: usize,
+        destination: Destination,
+    },
     DrawIndices,
     Clear,
 }
@@ -231,6 +499,10 @@ pub struct State {
     pub number: String,
     pub term: String,
     pub mode: Mode,
+    pub sort: SearchSort,
+    /// If true, a crate was selected with the cursor keys
+    pub selecting: bool,
+    pub history: History,
 }
 
 impl State {
@@ -248,7 +520,7 @@ impl<'a> Display for Indexed<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let dim = self.0.meta.dimension.clone().unwrap_or_default();
 
-        let (nw, ..) = desired_table_widths(&self.0.crates, &dim);
+        let (nw, ..) = desired_table_widths(self.0.page(), &dim);
         let center = (nw + 1) as u16;
         write!(
             f,
@@ -256,7 +528,8 @@ impl<'a> Display for Indexed<'a> {
             hide = cursor::Hide,
             align = cursor::Right(center)
         )?;
-        for i in (0..self.0.crates.len()).take(dim.height as usize) {
+        let offset = self.0.meta.offset;
+        for i in (offset..offset + self.0.page().len()).take(dim.height as usize) {
             let rendered = format!("|#{:3} #|", i);
             write!(
                 f,
@@ -269,3 +542,119 @@ impl<'a> Display for Indexed<'a> {
         Ok(())
     }
 }
+
+#[test]
+fn test_select() {
+    let krate = |name: &str| Crate {
+        name: name.into(),
+        ..Default::default()
+    };
+    let mut result = SearchResult::with_dimension(Dimension {
+        width: 80,
+        height: 3,
+    });
+    assert!(result.select(Movement::Down).is_none());
+    result.crates = vec![krate("a"), krate("b"), krate("c"), krate("d")];
+    result.meta.total = 7;
+    let select = |result: &mut SearchResult, m| result.select(m).map(|c| c.name.clone()).unwrap();
+    assert_eq!(select(&mut result, Movement::Up), "a");
+    assert_eq!(select(&mut result, Movement::Down), "b");
+    assert_eq!(result.rows(), "rows 1–3 of 7");
+    assert!(result.needs_more(Movement::PageDown));
+    assert_eq!(select(&mut result, Movement::PageDown), "d");
+    assert_eq!(result.rows(), "rows 4–4 of 7");
+    assert!(result.needs_more(Movement::Down));
+    assert_eq!(select(&mut result, Movement::Down), "d");
+    assert_eq!(select(&mut result, Movement::PageUp), "a");
+    assert_eq!(result.meta.offset, 0);
+
+    result.crates.push(krate("e"));
+    result.meta.total = 5;
+    assert!(!result.needs_more(Movement::PageDown));
+    assert_eq!(select(&mut result, Movement::PageDown), "d");
+    assert_eq!(select(&mut result, Movement::Down), "e");
+    assert!(!result.needs_more(Movement::Down));
+    assert_eq!(Indexed(&result).to_string().matches('#').count(), 2 * 2);
+    assert!(Indexed(&result).to_string().contains("#  4 #"));
+}
+
+#[test]
+fn test_fetch_beyond_max_page_size() {
+    assert_eq!(fetch_size(16), (16, 16));
+    assert_eq!(fetch_size(150), (100, 200));
+    let mut result = SearchResult::with_dimension(Dimension {
+        width: 80,
+        height: 150,
+    });
+    result.crates = vec![Crate::default(); 200];
+    result.meta.total = 1000;
+    assert_eq!(result.next_fetch(), (3, 200));
+    assert_eq!(result.rows(), "rows 1–150 of 1000");
+    assert!(result.needs_more(Movement::PageDown));
+}
+
+#[test]
+fn test_details_lines() {
+    let crate::structs::CrateResponse { krate, versions } =
+        serde_json::from_slice(include_bytes!("../../../tests/fixtures/crates-io-cli.json"))
+            .unwrap();
+    let crate::structs::OwnersResponse { users: owners } = serde_json::from_slice(include_bytes!(
+        "../../../tests/fixtures/crates-io-cli-owners.json"
+    ))
+    .unwrap();
+    let lines = Details {
+        krate,
+        versions,
+        owners,
+    }
+    .lines();
+    assert_eq!(lines.len(), 6);
+    assert!(lines[0].contains(" 4.0.0 (MIT)"));
+    assert_eq!(
+        lines[2],
+        "repository:    https://github.com/Byron/crates-io-cli"
+    );
+    assert_eq!(lines[3], "documentation: https://docs.rs/crates-io-cli");
+    assert_eq!(lines[4], "versions:      4.0.0, 2.0.0-alpha.1");
+    assert_eq!(lines[5], "owners:        Byron, github:org:team");
+}
+
+#[test]
+fn test_destination_url() {
+    let krate = Crate {
+        name: "a".into(),
+        max_version: "1.0.0".into(),
+        repository: Some("https://github.com/o/a".into()),
+        ..Default::default()
+    };
+    let url = |d: Destination| d.url("https://crates.io", &krate);
+    assert_eq!(
+        url(Destination::CratesIo).unwrap(),
+        "https://crates.io/crates/a/1.0.0"
+    );
+    assert_eq!(url(Destination::Docs).unwrap(), "https://docs.rs/a/1.0.0");
+    assert_eq!(
+        url(Destination::Repository).unwrap(),
+        "https://github.com/o/a"
+    );
+    assert!(url(Destination::Homepage).is_none());
+}
+
+#[test]
+fn test_copy_dependency_line() {
+    let mut krate = Crate {
+        name: "a".into(),
+        max_version: "2.0.0-rc.1".into(),
+        ..Default::default()
+    };
+    assert_eq!(dependency_line(&krate, false), r#"a = "2.0.0-rc.1""#);
+    krate.max_stable_version = Some("1.2.3".into());
+    assert_eq!(
+        dependency_line(&krate, true),
+        r#"a = { version = "1.2.3", features = [] }"#
+    );
+    assert_eq!(base64(b"a = \"1\""), "YSA9ICIxIg==");
+    assert_eq!(base64(b"ab"), "YWI=");
+    assert_eq!(base64(b"abc"), "YWJj");
+    assert_eq!(CopyToClipboard("abc").to_string(), "\x1b]52;c;YWJj\x07");
+}
@@ -0,0 +1,104 @@
+use super::error::Error;
+use crate::{
+    args::OutputKind,
+    http_utils::{remote_call, CallResult},
+    output::{print_items, ListItem},
+    structs::{Version, VersionsResponse},
+};
+use futures::Future;
+use prettytable::Row;
+use std::sync::{Arc, Mutex};
+use tokio_core::reactor;
+use tokio_curl::Session;
+
+impl ListItem for Version {
+    fn titles(items: &[Self]) -> Row {
+        let total: i64 = items.iter().map(|v| v.downloads).sum();
+        row![b -> "Version", b -> "Released", b -> "Yanked", b -> "License", b -> "Size", b ->
+            format!("Downloads (total={})", total)]
+    }
+
+    fn row(&self) -> Row {
+        row![
+            self.num,
+            self.created_at,
+            if self.yanked { "yes" } else { "no" },
+            self.license.as_deref().unwrap_or_default(),
+            self.crate_size.map(|s| s.to_string()).unwrap_or_default(),
+            self.downloads
+        ]
+    }
+}
+
+/// Which versions to leave out when listing them
+#[derive(Default, Clone, Copy)]
+pub struct Exclude {
+    pub yanked: bool,
+    pub prereleases: bool,
+}
+
+impl Exclude {
+    fn keeps(&self, v: &Version) -> bool {
+        !(self.yanked && v.yanked || self.prereleases && is_prerelease(&v.num))
+    }
+}
+
+fn is_prerelease(version: &str) -> bool {
+    version.split('+').next().unwrap_or_default().contains('-')
+}
+
+fn versions_from_callresult_buf(buf: &[u8]) -> Result<Vec<Version>, Error> {
+    let VersionsResponse { versions } = serde_json::from_slice(buf)?;
+    Ok(versions)
+}
+
+fn versions_from_callresult(name: &str, c: CallResult) -> Result<Vec<Version>, Error> {
+    let (buf, mut easy) = c;
+    if let Ok(404) = easy.response_code() {
+        return Err(Error::UnknownCrate(name.to_owned()));
+    }
+    let buf = buf.lock().unwrap();
+    versions_from_callresult_buf(&buf)
+}
+
+pub fn crate_versions(
+    name: String,
+    api_url: &str,
+    session: Arc<Mutex<Session>>,
+) -> Box<dyn Future<Item = Vec<Version>, Error = Error> + Send> {
+    Box::new(
+        remote_call(
+            &format!(
+                "{}/api/v1/crates/{}/versions",
+                api_url,
+                urlencoding::encode(&name)
+            ),
+            session,
+        )
+        .map_err(Into::into)
+        .and_then(move |c| versions_from_callresult(&name, c)),
+    )
+}
+
+pub fn handle_versions(
+    name: String,
+    exclude: Exclude,
+    api_url: &str,
+    output_format: OutputKind,
+) -> Result<(), Error> {
+    let mut reactor = reactor::Core::new().map_err(Error::ReactorInit)?;
+    let session = Arc::new(Mutex::new(Session::new(reactor.handle())));
+    let fut = crate_versions(name, api_url, session).and_then(|versions| {
+        let versions: Vec<_> = versions.into_iter().filter(|v| exclude.keeps(v)).collect();
+        print_items(output_format, &versions).map_err(Into::into)
+    });
+    reactor.run(fut)
+}
+
+#[test]
+fn test_is_prerelease() {
+    assert!(is_prerelease("2.0.0-alpha.1"));
+    assert!(is_prerelease("1.0.0-rc.1+build-5"));
+    assert!(!is_prerelease("1.0.0"));
+    assert!(!is_prerelease("1.0.0+build-5"));
+}
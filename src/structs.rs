@@ -1,3 +1,7 @@
+#[cfg(any(feature = "list", feature = "search"))]
+use crate::output::ListItem;
+#[cfg(any(feature = "list", feature = "search"))]
+use prettytable::Row;
 use serde_derive::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize)]
 pub struct Crates {
@@ -5,15 +9,216 @@ pub struct Crates {
     pub meta: Meta,
 }
 
+/// A crate as returned by the crates.io API, either in listings or on its own.
+///
+/// Fields only available in some of the responses are optional.
 #[derive(Serialize, Deserialize, Clone, Default)]
 pub struct Crate {
+    #[serde(default)]
+    pub badges: Option<Vec<Badge>>,
+    #[serde(default)]
+    pub categories: Option<Vec<String>>,
+    #[serde(default)]
+    pub created_at: Option<String>,
     pub description: Option<String>,
+    #[serde(default)]
+    pub documentation: Option<String>,
     pub downloads: i64,
+    /// Only set in search results, true if the name matches the query exactly
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exact_match: Option<bool>,
+    #[serde(default)]
+    pub homepage: Option<String>,
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub keywords: Option<Vec<String>>,
+    /// The license of the most recent version
+    #[serde(default)]
+    pub license: Option<String>,
+    #[serde(default)]
+    pub links: Option<CrateLinks>,
+    #[serde(default)]
+    pub max_stable_version: Option<String>,
     pub max_version: String,
     pub name: String,
+    #[serde(default)]
+    pub newest_version: Option<String>,
+    #[serde(default)]
+    pub recent_downloads: Option<i64>,
+    #[serde(default)]
+    pub repository: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    /// The ids of all versions, if requested
+    #[serde(default)]
+    pub versions: Option<Vec<u32>>,
+}
+
+#[cfg(any(feature = "list", feature = "search"))]
+impl ListItem for Crate {
+    fn titles(items: &[Self]) -> Row {
+        let total: i64 = items.iter().map(|c| c.downloads).sum();
+        row![b -> "Name", b -> "Description", b ->
+            format!("Downloads (total={})" , total), b -> "MaxVersion"]
+    }
+
+    fn row(&self) -> Row {
+        row![
+            self.name,
+            self.description.as_deref().unwrap_or_default(),
+            self.downloads,
+            self.max_version
+        ]
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Badge {
+    pub badge_type: String,
+    #[serde(default)]
+    pub attributes: serde_json::Value,
+}
+
+/// Paths to API endpoints with more information about a crate
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct CrateLinks {
+    #[serde(default)]
+    pub owner_team: Option<String>,
+    #[serde(default)]
+    pub owner_user: Option<String>,
+    #[serde(default)]
+    pub owners: Option<String>,
+    #[serde(default)]
+    pub reverse_dependencies: Option<String>,
+    #[serde(default)]
+    pub version_downloads: Option<String>,
+    #[serde(default)]
+    pub versions: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct Meta {
     pub total: u32,
 }
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct User {
+    pub id: u32,
+    pub login: String,
+    pub name: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct UserResponse {
+    pub user: User,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Team {
+    pub id: u32,
+    pub login: String,
+    pub name: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TeamResponse {
+    pub team: Team,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Owner {
+    pub id: u32,
+    pub login: String,
+    /// Either 'user' or 'team'
+    pub kind: String,
+    pub name: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct OwnersResponse {
+    pub users: Vec<Owner>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Version {
+    pub id: u32,
+    #[serde(rename = "crate")]
+    pub krate: String,
+    pub num: String,
+    pub downloads: i64,
+    pub created_at: String,
+    pub updated_at: String,
+    pub yanked: bool,
+    pub license: Option<String>,
+    pub crate_size: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct VersionsResponse {
+    pub versions: Vec<Version>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct VersionDownloads {
+    /// The id of the version
+    pub version: u32,
+    pub downloads: i64,
+    pub date: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtraDownloads {
+    pub downloads: i64,
+    pub date: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DownloadsMeta {
+    /// Downloads of versions not listed in `version_downloads`
+    pub extra_downloads: Vec<ExtraDownloads>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DownloadsResponse {
+    pub version_downloads: Vec<VersionDownloads>,
+    pub meta: DownloadsMeta,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Dependency {
+    pub version_id: u32,
+    pub crate_id: String,
+    pub req: String,
+    pub optional: bool,
+    pub kind: String,
+    pub downloads: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ReverseDependencies {
+    pub dependencies: Vec<Dependency>,
+    pub versions: Vec<Version>,
+    pub meta: Meta,
+}
+
+/// A crate version depending on another crate, assembled from a `Dependency` and the `Version` it belongs to.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ReverseDependency {
+    pub name: String,
+    pub version: String,
+    pub req: String,
+    pub kind: String,
+    pub optional: bool,
+    pub downloads: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CrateResponse {
+    #[serde(rename = "crate")]
+    pub krate: Crate,
+    pub versions: Vec<Version>,
+}
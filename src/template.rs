@@ -0,0 +1,223 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+quick_error! {
+    #[derive(Debug)]
+    pub enum Error {
+        UnclosedField(position: usize) {
+            display("The field starting at position {} is not closed with '}}'", position)
+        }
+        UnopenedField(position: usize) {
+            display("The '}}' at position {} does not close a field - use '}}}}' for a literal '}}'", position)
+        }
+        EmptyField(position: usize) {
+            display("The field at position {} has no name", position)
+        }
+        UnknownField(name: String, available: Vec<String>) {
+            display("There is no field named '{}' - available fields are: {}", name, available.join(", "))
+        }
+        Json(err: serde_json::Error) {
+            description("The item could not be converted into fields")
+            from()
+            cause(err)
+        }
+    }
+}
+
+/// The path to a field of a record, like `links.owners`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field(Vec<String>);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    Field(Field),
+}
+
+/// A template like `{name}@{max_version}` to render serializable records with,
+/// whose fields are named like the keys of their json representation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template(Vec<Segment>);
+
+impl FromStr for Template {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = s.char_indices().peekable();
+        while let Some((pos, c)) = chars.next() {
+            match c {
+                '{' if chars.peek().map(|(_, c)| *c) == Some('{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek().map(|(_, c)| *c) == Some('}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '}' => return Err(Error::UnopenedField(pos)),
+                '{' => {
+                    let mut name = String::new();
+                    let mut closed = false;
+                    for (_, c) in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        name.push(c);
+                    }
+                    if !closed {
+                        return Err(Error::UnclosedField(pos));
+                    }
+                    if name.trim().is_empty() {
+                        return Err(Error::EmptyField(pos));
+                    }
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Field(name.parse()?));
+                }
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+        Ok(Template(segments))
+    }
+}
+
+/// The value as it should be displayed as part of a line: strings without quotes, and nothing for null.
+pub fn plain(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Order values of the same type naturally, and otherwise null before booleans, numbers, strings,
+/// arrays and objects.
+pub fn compare(a: &Value, b: &Value) -> Ordering {
+    fn rank(v: &Value) -> u8 {
+        match v {
+            Value::Null => 0,
+            Value::Bool(_) => 1,
+            Value::Number(_) => 2,
+            Value::String(_) => 3,
+            Value::Array(_) => 4,
+            Value::Object(_) => 5,
+        }
+    }
+    match (a, b) {
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Array(a), Value::Array(b)) => a
+            .iter()
+            .zip(b.iter())
+            .map(|(a, b)| compare(a, b))
+            .find(|o| *o != Ordering::Equal)
+            .unwrap_or_else(|| a.len().cmp(&b.len())),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+impl FromStr for Field {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(Error::EmptyField(0));
+        }
+        Ok(Field(s.split('.').map(ToOwned::to_owned).collect()))
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.join("."))
+    }
+}
+
+impl Field {
+    /// Find this field in `value`, the json representation of a record.
+    pub fn lookup<'a>(&self, value: &'a Value) -> Result<&'a Value, Error> {
+        let path = &self.0;
+        path.iter()
+            .enumerate()
+            .try_fold(value, |value, (depth, key)| {
+                let field = match value {
+                    Value::Object(fields) => fields.get(key),
+                    Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+                    _ => None,
+                };
+                field.ok_or_else(|| {
+                    let available = match value {
+                        Value::Object(fields) => fields
+                            .keys()
+                            .map(|k| {
+                                path[..depth]
+                                    .iter()
+                                    .chain(Some(k))
+                                    .cloned()
+                                    .collect::<Vec<_>>()
+                                    .join(".")
+                            })
+                            .collect(),
+                        _ => Vec::new(),
+                    };
+                    Error::UnknownField(path[..=depth].join("."), available)
+                })
+            })
+    }
+}
+
+impl Template {
+    pub fn render<T>(&self, item: &T) -> Result<String, Error>
+    where
+        T: Serialize,
+    {
+        let value = serde_json::to_value(item)?;
+        self.0.iter().try_fold(String::new(), |mut out, segment| {
+            match segment {
+                Segment::Literal(s) => out.push_str(s),
+                Segment::Field(field) => out.push_str(&plain(field.lookup(&value)?)),
+            }
+            Ok(out)
+        })
+    }
+}
+
+#[test]
+fn test_template() {
+    let item = serde_json::json!({
+        "name": "crates-io-cli",
+        "max_version": "4.0.0",
+        "downloads": 42,
+        "homepage": null,
+        "links": {"owners": "/owners"},
+        "keywords": ["cli", "search"]
+    });
+    let render = |t: &str| t.parse::<Template>().and_then(|t| t.render(&item));
+    assert_eq!(
+        render("{name}@{max_version}").unwrap(),
+        "crates-io-cli@4.0.0"
+    );
+    assert_eq!(render("{{{ downloads }}}|{homepage}|").unwrap(), "{42}||");
+    assert_eq!(
+        render("{links.owners} {keywords.1}").unwrap(),
+        "/owners search"
+    );
+    assert!(matches!(render("{nme}"), Err(Error::UnknownField(name, _)) if name == "nme"));
+    assert!(matches!(render("{links.x}"), Err(Error::UnknownField(_, a)) if a == ["links.owners"]));
+    assert!(matches!(render("{name"), Err(Error::UnclosedField(0))));
+    assert!(matches!(render("name}"), Err(Error::UnopenedField(4))));
+    assert!(matches!(render("a{ }"), Err(Error::EmptyField(1))));
+}
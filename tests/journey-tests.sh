@@ -25,6 +25,12 @@ title "list"
       expect_run_sh $SUCCESSFULLY "test \$($exe list by-user $user_id | wc -l) -gt 200"
     }
   )
+  (when "the user is given by login"
+    user_login=Byron
+    it "resolves the login and lists all entries" && {
+      expect_run_sh $SUCCESSFULLY "test \$($exe list by-user $user_login | wc -l) -gt 200"
+    }
+  )
   (when "the user does not exist"
     user_id=0
     it "does not fail but lists nothing" && {
@@ -34,6 +40,16 @@ title "list"
   )
 )
 
+title "search"
+
+(when "standard output is not a terminal"
+  (when "the search term is piped with the --output=json and --limit flags"
+    it "prints as many results as the limit allows, as json" && {
+      expect_run_sh $SUCCESSFULLY "test \$(echo serde | $exe search --output=json --limit 3 | grep -c '^  {') -eq 3"
+    }
+  )
+)
+
 title "recent-changes"
 
 (when "a repository is specified"